@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::error::Error;
+use crate::utils::parse_number;
+
+/// Expected answers keyed by day and task, read from a TOML file such as:
+///
+/// ```toml
+/// [day8]
+/// task1 = "1753"
+/// task2 = 733
+/// ```
+///
+/// Only the subset of TOML needed for this layout is understood: `[dayN]`
+/// tables holding `taskN` keys with string or integer values.
+#[derive(Debug, PartialEq, Default)]
+pub struct Answers {
+    answers: HashMap<(usize, usize), String>,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Answers, Error> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| Error::input(format!("failed to read {}: {}", path.display(), e)))?;
+        Answers::parse(&content)
+    }
+
+    pub fn parse(input: &str) -> Result<Answers, Error> {
+        lazy_static! {
+            static ref TABLE_RE: Regex = Regex::new(r"^\[day(\d+)\]$").unwrap();
+            static ref ANSWER_RE: Regex =
+                Regex::new(r#"^task(\d+)\s*=\s*(?:"([^"\\]*)"|(-?\d+))\s*(?:#.*)?$"#).unwrap();
+        }
+        let mut answers = HashMap::new();
+        let mut day = None;
+        for (i, line) in input.lines().enumerate() {
+            let line = match line.split_once('#') {
+                Some((content, _)) if !content.contains('"') => content,
+                _ => line,
+            }
+            .trim();
+            if line.is_empty() {
+                continue;
+            }
+            let parsed = if let Some(caps) = TABLE_RE.captures(line) {
+                parse_number(&caps[1]).map(|d| day = Some(d))
+            } else if let Some(caps) = ANSWER_RE.captures(line) {
+                let answer = caps.get(2).or_else(|| caps.get(3)).unwrap().as_str();
+                match day {
+                    Some(day) => parse_number(&caps[1]).map(|task| {
+                        answers.insert((day, task), answer.to_string());
+                    }),
+                    None => Err(Error::parse("answer outside of a `[dayN]` table")),
+                }
+            } else {
+                Err(Error::parse(format!(
+                    "expected `[dayN]` or `taskN = \"answer\"`, found `{}`",
+                    line
+                )))
+            };
+            parsed.map_err(|e| e.at_line(i + 1))?;
+        }
+        Ok(Answers { answers })
+    }
+
+    pub fn get(&self, day: usize, task: usize) -> Option<&str> {
+        self.answers.get(&(day, task)).map(|a| a.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Answers;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse(
+            "# recorded answers
+[day1]
+task1 = \"514579\" # recorded
+task2 = 241861950 # from the example
+
+[day8]
+task2 = \"8 # not a comment\"",
+        )
+        .unwrap();
+        assert_eq!(answers.get(1, 1), Some("514579"));
+        assert_eq!(answers.get(1, 2), Some("241861950"));
+        assert_eq!(answers.get(8, 1), None);
+        assert_eq!(answers.get(8, 2), Some("8 # not a comment"));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            Answers::parse("task1 = \"1\"").err().unwrap().to_string(),
+            "line 1: answer outside of a `[dayN]` table"
+        );
+        assert!(Answers::parse("[day1]\ntask1 = one").is_err());
+    }
+}
@@ -0,0 +1,164 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_WARMUP: usize = 3;
+pub const DEFAULT_RUNS: usize = 100;
+
+#[derive(Debug, PartialEq)]
+pub struct BenchOptions {
+    pub warmup: usize,
+    pub runs: Option<usize>,
+    pub time: Option<Duration>,
+}
+
+impl BenchOptions {
+    /// The number of measured runs: `--runs` if given, unbounded when only a
+    /// time budget is set, otherwise the default.
+    fn max_runs(&self) -> usize {
+        match (self.runs, self.time) {
+            (Some(runs), _) => runs.max(1),
+            (None, Some(_)) => usize::MAX,
+            (None, None) => DEFAULT_RUNS,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let runs = sorted.len();
+        let median = if runs.is_multiple_of(2) {
+            (sorted[runs / 2 - 1] + sorted[runs / 2]) / 2
+        } else {
+            sorted[runs / 2]
+        };
+        let p95 = sorted[((runs as f64 * 0.95).ceil() as usize).max(1) - 1];
+        let mean = sorted.iter().sum::<Duration>() / runs as u32;
+        let variance = if runs > 1 {
+            sorted
+                .iter()
+                .map(|s| (s.as_secs_f64() - mean.as_secs_f64()).powi(2))
+                .sum::<f64>()
+                / (runs - 1) as f64
+        } else {
+            0.0
+        };
+        Stats {
+            runs,
+            min: sorted[0],
+            median,
+            mean,
+            p95,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "runs: {}, min: {:.2?}, median: {:.2?}, mean: {:.2?}, p95: {:.2?}, stddev: {:.2?}",
+            self.runs, self.min, self.median, self.mean, self.p95, self.stddev
+        )
+    }
+}
+
+/// Runs `f` through the warm-up and measured runs described by `options`.
+/// The first call doubles as a warm-up run; if it fails the error is returned
+/// straight away rather than timing a failing task.
+pub fn measure<T, E>(
+    f: impl Fn() -> Result<T, E>,
+    options: &BenchOptions,
+) -> Result<(T, Stats), E> {
+    let result = f()?;
+    for _ in 1..options.warmup {
+        let _ = f();
+    }
+    let max_runs = options.max_runs();
+    let started = Instant::now();
+    let mut samples = vec![];
+    while samples.len() < max_runs
+        && options
+            .time
+            .is_none_or(|t| samples.is_empty() || started.elapsed() < t)
+    {
+        let now = Instant::now();
+        let _ = f();
+        samples.push(now.elapsed());
+    }
+    Ok((result, Stats::from_samples(&samples)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::time::Duration;
+
+    use super::{measure, BenchOptions, Stats};
+
+    fn ms(v: u64) -> Duration {
+        Duration::from_millis(v)
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples(&[ms(4), ms(1), ms(3), ms(2)]);
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.min, ms(1));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+        assert_eq!(stats.p95, ms(4));
+        assert_eq!(stats.stddev.as_micros(), 1290);
+    }
+
+    #[test]
+    fn test_single_sample() {
+        let stats = Stats::from_samples(&[ms(7)]);
+        assert_eq!(stats.median, ms(7));
+        assert_eq!(stats.p95, ms(7));
+        assert_eq!(stats.stddev, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_measure_runs() {
+        let calls = Cell::new(0);
+        let options = BenchOptions {
+            warmup: 3,
+            runs: Some(10),
+            time: None,
+        };
+        let (res, stats) = measure(
+            || {
+                calls.set(calls.get() + 1);
+                Ok::<_, ()>(5)
+            },
+            &options,
+        )
+        .unwrap();
+        assert_eq!(res, 5);
+        assert_eq!(stats.runs, 10);
+        assert_eq!(calls.get(), 13);
+    }
+
+    #[test]
+    fn test_measure_error() {
+        let options = BenchOptions {
+            warmup: 3,
+            runs: None,
+            time: None,
+        };
+        assert_eq!(measure(|| Err::<(), _>("bad"), &options), Err("bad"));
+    }
+}
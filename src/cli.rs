@@ -0,0 +1,351 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::bench::{BenchOptions, DEFAULT_WARMUP};
+use crate::report::Format;
+
+pub const USAGE: &str = "Usage: aoc run [--all | --day <N|A..B|A..=B>]... [--task <N>] [--input <PATH>] [--inputs-dir <DIR>]
+               [--bench [--warmup <N>] [--runs <N>] [--time <MS>]] [--format <FORMAT>]
+               [--answers <PATH>] [--jobs <N>]
+
+Options:
+    --all               Run every registered day (the default)
+    --day <DAYS>        Run a single day or a range of days, may be repeated
+    --task <N>          Only run the given task of each selected day
+    --input <PATH>      Read the puzzle input from a file, or `-` for stdin (single day only)
+    --inputs-dir <DIR>  Look for inputs named `dayNN.txt` in this directory [default: inputs]
+    --bench             Time each task over repeated runs and report statistics
+    --warmup <N>        Unmeasured runs before timing starts [default: 3]
+    --runs <N>          Measured runs per task [default: 100, or unbounded with --time]
+    --time <MS>         Stop measuring a task once this many milliseconds have passed
+    --format <FORMAT>   Output format: text, json, csv or markdown [default: text]
+    --answers <PATH>    Check each answer against a TOML file of expected answers
+    --jobs <N>          Run days and tasks on up to N threads, reporting in day/task order [default: 1]
+    -h, --help          Print this message";
+
+pub const DEFAULT_INPUTS_DIR: &str = "inputs";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(Options),
+    Help,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Options {
+    pub days: DaySelection,
+    pub task: Option<usize>,
+    pub input: Option<InputSource>,
+    pub inputs_dir: PathBuf,
+    pub bench: Option<BenchOptions>,
+    pub format: Format,
+    pub answers: Option<PathBuf>,
+    pub jobs: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+/// The days given with `--day`. Ranges are kept as ranges rather than
+/// expanded, so an oversized range costs nothing until it is checked against
+/// the registered days.
+#[derive(Debug, PartialEq)]
+pub enum DaySelection {
+    All,
+    Days(Vec<RangeInclusive<usize>>),
+}
+
+impl DaySelection {
+    pub fn includes(&self, day: usize) -> bool {
+        match self {
+            DaySelection::All => true,
+            DaySelection::Days(ranges) => ranges.iter().any(|r| r.contains(&day)),
+        }
+    }
+
+    /// Whether `day` was named on its own rather than as part of a range.
+    pub fn names(&self, day: usize) -> bool {
+        match self {
+            DaySelection::All => false,
+            DaySelection::Days(ranges) => ranges.iter().any(|r| r == &(day..=day)),
+        }
+    }
+
+    /// The selected day, if exactly one is selected.
+    fn single(&self) -> Option<usize> {
+        match self {
+            DaySelection::All => None,
+            DaySelection::Days(ranges) => {
+                let day = *ranges.first()?.start();
+                ranges.iter().all(|r| r == &(day..=day)).then_some(day)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct UsageError(String);
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Command, UsageError> {
+    let mut args = args.into_iter().peekable();
+    if args.next_if(|a| a == "run").is_none() {
+        if let Some(a) = args.peek() {
+            if !a.starts_with('-') {
+                return Err(UsageError(format!("unknown command `{}`", a)));
+            }
+        }
+    }
+    let mut all = false;
+    let mut days: Vec<RangeInclusive<usize>> = vec![];
+    let mut task = None;
+    let mut input = None;
+    let mut inputs_dir = PathBuf::from(DEFAULT_INPUTS_DIR);
+    let mut bench = false;
+    let mut warmup = None;
+    let mut runs = None;
+    let mut time = None;
+    let mut format = Format::Text;
+    let mut answers = None;
+    let mut jobs = 1;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--all" => all = true,
+            "--day" => days.push(parse_days(&expect_value(&arg, args.next())?)?),
+            "--task" => task = Some(parse_number(&expect_value(&arg, args.next())?)?),
+            "--input" => {
+                input = Some(match expect_value(&arg, args.next())?.as_str() {
+                    "-" => InputSource::Stdin,
+                    path => InputSource::File(PathBuf::from(path)),
+                })
+            }
+            "--inputs-dir" => inputs_dir = PathBuf::from(expect_value(&arg, args.next())?),
+            "--bench" => bench = true,
+            "--warmup" => warmup = Some(parse_number(&expect_value(&arg, args.next())?)?),
+            "--runs" => runs = Some(parse_number(&expect_value(&arg, args.next())?)?),
+            "--time" => {
+                let ms = parse_number(&expect_value(&arg, args.next())?)?;
+                time = Some(Duration::from_millis(ms as u64))
+            }
+            "--format" => {
+                let name = expect_value(&arg, args.next())?;
+                format = Format::parse(&name)
+                    .ok_or_else(|| UsageError(format!("unknown format `{}`", name)))?
+            }
+            "--answers" => answers = Some(PathBuf::from(expect_value(&arg, args.next())?)),
+            "--jobs" => {
+                jobs = parse_number(&expect_value(&arg, args.next())?)?;
+                if jobs == 0 {
+                    return Err(UsageError("`--jobs` must be at least 1".to_string()));
+                }
+            }
+            _ => return Err(UsageError(format!("unknown argument `{}`", arg))),
+        }
+    }
+    if all && !days.is_empty() {
+        return Err(UsageError(
+            "`--all` cannot be combined with `--day`".to_string(),
+        ));
+    }
+    let days = if days.is_empty() {
+        DaySelection::All
+    } else {
+        DaySelection::Days(days)
+    };
+    if input.is_some() && days.single().is_none() {
+        return Err(UsageError(
+            "`--input` requires exactly one day to be selected".to_string(),
+        ));
+    }
+    if !bench && (warmup.is_some() || runs.is_some() || time.is_some()) {
+        return Err(UsageError(
+            "`--warmup`, `--runs` and `--time` require `--bench`".to_string(),
+        ));
+    }
+    Ok(Command::Run(Options {
+        days,
+        task,
+        input,
+        inputs_dir,
+        bench: bench.then(|| BenchOptions {
+            warmup: warmup.unwrap_or(DEFAULT_WARMUP),
+            runs,
+            time,
+        }),
+        format,
+        answers,
+        jobs,
+    }))
+}
+
+fn expect_value(flag: &str, value: Option<String>) -> Result<String, UsageError> {
+    value.ok_or_else(|| UsageError(format!("`{}` expects a value", flag)))
+}
+
+fn parse_number(value: &str) -> Result<usize, UsageError> {
+    value
+        .parse::<usize>()
+        .map_err(|_| UsageError(format!("`{}` is not a valid number", value)))
+}
+
+fn parse_days(value: &str) -> Result<RangeInclusive<usize>, UsageError> {
+    let range = if let Some((start, end)) = value.split_once("..=") {
+        parse_number(start)?..=parse_number(end)?
+    } else if let Some((start, end)) = value.split_once("..") {
+        let end = parse_number(end)?;
+        if end == 0 {
+            return Err(UsageError(format!("`{}` is an empty range", value)));
+        }
+        parse_number(start)?..=end - 1
+    } else {
+        let day = parse_number(value)?;
+        day..=day
+    };
+    if range.is_empty() {
+        return Err(UsageError(format!("`{}` is an empty range", value)));
+    }
+    Ok(range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, UsageError> {
+        parse_args(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn test_defaults_to_all() {
+        let expected = || {
+            Command::Run(Options {
+                days: DaySelection::All,
+                task: None,
+                input: None,
+                inputs_dir: PathBuf::from(DEFAULT_INPUTS_DIR),
+                bench: None,
+                format: Format::Text,
+                answers: None,
+                jobs: 1,
+            })
+        };
+        assert_eq!(parse(&[]), Ok(expected()));
+        assert_eq!(parse(&["run", "--all"]), Ok(expected()));
+    }
+
+    #[test]
+    fn test_day_and_task() {
+        assert_eq!(
+            parse(&["run", "--day", "7", "--task", "2"]),
+            Ok(Command::Run(Options {
+                days: DaySelection::Days(vec![7..=7]),
+                task: Some(2),
+                input: None,
+                inputs_dir: PathBuf::from(DEFAULT_INPUTS_DIR),
+                bench: None,
+                format: Format::Text,
+                answers: None,
+                jobs: 1,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_input_sources() {
+        assert_eq!(
+            parse(&["run", "--day", "1", "--input", "-", "--inputs-dir", "mine"]),
+            Ok(Command::Run(Options {
+                days: DaySelection::Days(vec![1..=1]),
+                task: None,
+                input: Some(InputSource::Stdin),
+                inputs_dir: PathBuf::from("mine"),
+                bench: None,
+                format: Format::Text,
+                answers: None,
+                jobs: 1,
+            }))
+        );
+        assert!(parse(&["run", "--input", "day01.txt"]).is_err());
+        assert!(parse(&["run", "--day", "1..=2", "--input", "day01.txt"]).is_err());
+        assert!(parse(&["run", "--day", "1", "--day", "1..=1", "--input", "-"]).is_ok());
+    }
+
+    #[test]
+    fn test_bench() {
+        let bench = |args: &[&str]| match parse(args) {
+            Ok(Command::Run(options)) => options.bench,
+            _ => panic!("expected a run command"),
+        };
+        assert_eq!(
+            bench(&["run", "--bench"]),
+            Some(BenchOptions {
+                warmup: DEFAULT_WARMUP,
+                runs: None,
+                time: None
+            })
+        );
+        assert_eq!(
+            bench(&["run", "--bench", "--warmup", "0", "--time", "500"]),
+            Some(BenchOptions {
+                warmup: 0,
+                runs: None,
+                time: Some(Duration::from_millis(500))
+            })
+        );
+        assert!(parse(&["run", "--runs", "10"]).is_err());
+    }
+
+    #[test]
+    fn test_format() {
+        match parse(&["run", "--format", "markdown"]) {
+            Ok(Command::Run(options)) => assert_eq!(options.format, Format::Markdown),
+            _ => panic!("expected a run command"),
+        }
+        assert!(parse(&["run", "--format", "xml"]).is_err());
+    }
+
+    #[test]
+    fn test_jobs() {
+        match parse(&["run", "--jobs", "4"]) {
+            Ok(Command::Run(options)) => assert_eq!(options.jobs, 4),
+            _ => panic!("expected a run command"),
+        }
+        assert!(parse(&["run", "--jobs", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_ranges() {
+        assert_eq!(parse_days("3..=5"), Ok(3..=5));
+        assert_eq!(parse_days("3..5"), Ok(3..=4));
+        assert_eq!(parse_days("1..=4000000000"), Ok(1..=4_000_000_000));
+        assert!(parse_days("5..3").is_err());
+        assert!(parse_days("x").is_err());
+    }
+
+    #[test]
+    fn test_selection() {
+        let days = DaySelection::Days(vec![3..=5, 9..=9]);
+        assert!(days.includes(4) && days.includes(9) && !days.includes(6));
+        assert!(days.names(9) && !days.names(4));
+        assert_eq!(days.single(), None);
+        assert_eq!(DaySelection::Days(vec![2..=2, 2..=2]).single(), Some(2));
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(parse(&["run", "--all", "--day", "1"]).is_err());
+        assert!(parse(&["run", "--day"]).is_err());
+        assert!(parse(&["walk"]).is_err());
+    }
+}
@@ -1,34 +1,40 @@
-use std::iter;
-
-use crate::Day;
+use crate::error::Error;
+use crate::utils::{parse_lines, parse_number};
+use crate::{Day, Task};
 
 pub struct Day1 {}
 
-impl<'a> Day<'a> for Day1 {
-    fn get_tasks(&self) -> Vec<(usize, &dyn Fn() -> String)> {
-        vec![(1, &|| task(2)), (2, &|| task(3))]
+impl Day for Day1 {
+    type Input = Vec<u32>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        get_input(input)
+    }
+
+    fn get_tasks(&self) -> Vec<(usize, &Task<Self::Input>)> {
+        vec![
+            (1, &|input: &Vec<u32>| task(input, 2)),
+            (2, &|input: &Vec<u32>| task(input, 3)),
+        ]
     }
 
-    fn get_day_number(&self) -> usize {
-        1
+    fn get_embedded_input(&self) -> Option<&'static str> {
+        Some(INPUT)
     }
 }
 
-fn task(elems: usize) -> String {
-    find_first_to_sum(&get_input(), 2020, elems)
-        .unwrap()
-        .to_string()
+fn task(input: &Vec<u32>, elems: usize) -> Result<String, Error> {
+    find_first_to_sum(input, 2020, elems)
+        .map(|r| r.to_string())
+        .ok_or_else(|| Error::no_solution(format!("no {} entries sum to 2020", elems)))
 }
 
-fn get_input() -> Vec<u32> {
-    INPUT
-        .split_whitespace()
-        .map(|s| s.parse::<u32>().unwrap())
-        .collect()
+fn get_input(input: &str) -> Result<Vec<u32>, Error> {
+    parse_lines(input.lines(), |l| parse_number(l.trim()))
 }
 
 fn find_first_to_sum(input: &Vec<u32>, target: u32, num_elems: usize) -> Option<u64> {
-    let super_input: Vec<&Vec<u32>> = iter::repeat(input).take(num_elems).collect();
+    let super_input: Vec<&Vec<u32>> = std::iter::repeat_n(input, num_elems).collect();
     fn inner_loop(
         acc_input: &[&Vec<u32>],
         sum_acc: u32,
@@ -52,11 +58,11 @@ fn find_first_to_sum(input: &Vec<u32>, target: u32, num_elems: usize) -> Option<
                     return Some(u);
                 }
             }
-            return None;
+            None
         }
     }
 
-    return inner_loop(&super_input[..], 0, 1, target);
+    inner_loop(&super_input[..], 0, 1, target)
 }
 
 const INPUT: &str = include_str!("input.txt");
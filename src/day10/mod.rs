@@ -0,0 +1,142 @@
+use std::str::Lines;
+
+use crate::error::Error;
+use crate::utils::{parse_lines, parse_number};
+use crate::{Day, Task};
+
+pub struct Day10 {}
+
+impl Day for Day10 {
+    type Input = Adapters;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Adapters::parse(input.lines())
+    }
+
+    fn get_tasks(&self) -> Vec<(usize, &Task<Self::Input>)> {
+        vec![(1, &task1), (2, &task2)]
+    }
+}
+
+fn task1(adapters: &Adapters) -> Result<String, Error> {
+    let counts = adapters
+        .difference_counts()
+        .ok_or_else(|| Error::no_solution("the adapters cannot all be chained"))?;
+    Ok((counts[1] * counts[3]).to_string())
+}
+
+fn task2(adapters: &Adapters) -> Result<String, Error> {
+    Ok(adapters.count_arrangements().to_string())
+}
+
+pub struct Adapters {
+    /// The outlet, every adapter in ascending order, then the device.
+    chain: Vec<u32>,
+}
+
+impl Adapters {
+    fn parse(lines: Lines) -> Result<Adapters, Error> {
+        let mut chain: Vec<u32> = parse_lines(lines, parse_number)?;
+        chain.sort_unstable();
+        let device = chain.last().map_or(3, |max| max + 3);
+        chain.insert(0, 0);
+        chain.push(device);
+        Ok(Adapters { chain })
+    }
+
+    /// Counts the joltage differences between neighbouring adapters when
+    /// every one of them is used, or `None` if some gap is wider than 3.
+    fn difference_counts(&self) -> Option<[usize; 4]> {
+        let mut counts = [0; 4];
+        for pair in self.chain.windows(2) {
+            *counts.get_mut((pair[1] - pair[0]) as usize)? += 1;
+        }
+        Some(counts)
+    }
+
+    /// Counts the arrangements reaching each adapter from the ones up to 3
+    /// jolts below it; the count at the device is the answer.
+    fn count_arrangements(&self) -> u64 {
+        let mut ways: Vec<u64> = vec![0; self.chain.len()];
+        ways[0] = 1;
+        for i in 1..self.chain.len() {
+            ways[i] = (i.saturating_sub(3)..i)
+                .filter(|j| self.chain[i] - self.chain[*j] <= 3)
+                .map(|j| ways[j])
+                .sum();
+        }
+        ways[self.chain.len() - 1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST1_INPUT: &str = "16
+10
+15
+5
+1
+11
+7
+19
+6
+12
+4";
+
+    const TEST2_INPUT: &str = "28
+33
+18
+42
+31
+14
+46
+20
+48
+47
+24
+23
+49
+45
+19
+38
+39
+11
+1
+32
+25
+35
+8
+17
+7
+9
+4
+2
+34
+10
+3";
+
+    #[test]
+    fn test_difference_counts() {
+        let adapters = Adapters::parse(TEST1_INPUT.lines()).unwrap();
+        assert_eq!(adapters.difference_counts(), Some([0, 7, 0, 5]));
+        let adapters = Adapters::parse(TEST2_INPUT.lines()).unwrap();
+        assert_eq!(task1(&adapters), Ok("220".to_string()));
+    }
+
+    #[test]
+    fn test_gap_too_wide() {
+        let adapters = Adapters::parse("1\n5".lines()).unwrap();
+        assert_eq!(adapters.difference_counts(), None);
+        assert_eq!(adapters.count_arrangements(), 0);
+    }
+
+    #[test]
+    fn test_count_arrangements() {
+        let adapters = Adapters::parse(TEST1_INPUT.lines()).unwrap();
+        assert_eq!(adapters.count_arrangements(), 8);
+        let adapters = Adapters::parse(TEST2_INPUT.lines()).unwrap();
+        assert_eq!(adapters.count_arrangements(), 19208);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::error::Error;
+use crate::grid::{Grid, DIRECTIONS};
+use crate::{Day, Task};
+
+pub struct Day11 {}
+
+impl Day for Day11 {
+    type Input = Grid<Seat>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Grid::parse(input, Seat::from_char)
+    }
+
+    fn get_tasks(&self) -> Vec<(usize, &Task<Self::Input>)> {
+        vec![
+            (1, &|layout: &Grid<Seat>| task(layout, Rule::Adjacent)),
+            (2, &|layout: &Grid<Seat>| task(layout, Rule::LineOfSight)),
+        ]
+    }
+}
+
+fn task(layout: &Grid<Seat>, rule: Rule) -> Result<String, Error> {
+    Ok(settle(layout, rule).to_string())
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Seat {
+    Floor,
+    Empty,
+    Occupied,
+}
+
+impl Seat {
+    fn from_char(c: char) -> Option<Seat> {
+        match c {
+            '.' => Some(Seat::Floor),
+            'L' => Some(Seat::Empty),
+            '#' => Some(Seat::Occupied),
+            _ => None,
+        }
+    }
+}
+
+enum Rule {
+    /// Seats see the eight seats around them and empty at four occupied.
+    Adjacent,
+    /// Seats see the first seat in each direction, looking past the floor,
+    /// and empty at five occupied.
+    LineOfSight,
+}
+
+impl Rule {
+    fn tolerance(&self) -> usize {
+        match self {
+            Rule::Adjacent => 4,
+            Rule::LineOfSight => 5,
+        }
+    }
+
+    fn visible_seats(&self, layout: &Grid<Seat>, pos: (usize, usize)) -> Vec<usize> {
+        DIRECTIONS
+            .iter()
+            .filter_map(|d| {
+                let mut next = layout.step(pos, *d);
+                if let Rule::LineOfSight = self {
+                    while let Some((x, y)) = next {
+                        if layout.get(x, y) != Some(&Seat::Floor) {
+                            break;
+                        }
+                        next = layout.step((x, y), *d);
+                    }
+                }
+                next
+            })
+            .filter(|(x, y)| layout.get(*x, *y) != Some(&Seat::Floor))
+            .map(|(x, y)| y * layout.width() + x)
+            .collect()
+    }
+}
+
+/// Applies `rule` to every seat at once until nobody moves, then counts the
+/// occupied seats. Which seats each seat can see never changes, so that is
+/// worked out once up front.
+fn settle(layout: &Grid<Seat>, rule: Rule) -> usize {
+    let visible: Vec<Vec<usize>> = layout
+        .positions()
+        .map(|pos| rule.visible_seats(layout, pos))
+        .collect();
+    let mut seats = layout.cells().to_vec();
+    loop {
+        let next: Vec<Seat> = seats
+            .iter()
+            .zip(&visible)
+            .map(|(seat, visible)| {
+                let occupied = || {
+                    visible
+                        .iter()
+                        .filter(|i| seats[**i] == Seat::Occupied)
+                        .count()
+                };
+                match seat {
+                    Seat::Empty if occupied() == 0 => Seat::Occupied,
+                    Seat::Occupied if occupied() >= rule.tolerance() => Seat::Empty,
+                    _ => *seat,
+                }
+            })
+            .collect();
+        if next == seats {
+            return seats.iter().filter(|s| **s == Seat::Occupied).count();
+        }
+        seats = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day11::{settle, Day11, Rule};
+    use crate::Day;
+
+    const TESTINPUT: &str = "L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL";
+
+    #[test]
+    fn test_adjacent() {
+        let layout = Day11 {}.parse(TESTINPUT).unwrap();
+        assert_eq!(settle(&layout, Rule::Adjacent), 37);
+    }
+
+    #[test]
+    fn test_line_of_sight() {
+        let layout = Day11 {}.parse(TESTINPUT).unwrap();
+        assert_eq!(settle(&layout, Rule::LineOfSight), 26);
+    }
+
+    #[test]
+    fn test_visible_seats() {
+        let layout = Day11 {}
+            .parse(".##.##.\n#.#.#.#\n##...##\n...L...\n##...##\n#.#.#.#\n.##.##.")
+            .unwrap();
+        assert!(Rule::LineOfSight.visible_seats(&layout, (3, 3)).is_empty());
+        assert!(Day11 {}.parse("L.\n.x").is_err());
+    }
+}
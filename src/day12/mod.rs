@@ -0,0 +1,154 @@
+use crate::error::Error;
+use crate::utils::{parse_lines, parse_number};
+use crate::{Day, Task};
+
+pub struct Day12 {}
+
+impl Day for Day12 {
+    type Input = Vec<Instruction>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_lines(input.lines(), Instruction::parse)
+    }
+
+    fn get_tasks(&self) -> Vec<(usize, &Task<Self::Input>)> {
+        vec![
+            (1, &|input: &Vec<Instruction>| task(input, Model::Heading)),
+            (2, &|input: &Vec<Instruction>| task(input, Model::Waypoint)),
+        ]
+    }
+}
+
+fn task(instructions: &[Instruction], model: Model) -> Result<String, Error> {
+    Ok(navigate(instructions, model).to_string())
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Instruction {
+    North(i32),
+    South(i32),
+    East(i32),
+    West(i32),
+    /// Turn left by this many quarter turns.
+    Left(u32),
+    /// Turn right by this many quarter turns.
+    Right(u32),
+    Forward(i32),
+}
+use crate::day12::Instruction::*;
+impl Instruction {
+    fn parse(inst: &str) -> Result<Instruction, Error> {
+        let action = inst
+            .chars()
+            .next()
+            .ok_or_else(|| Error::parse("expected an instruction, found an empty line"))?;
+        let arg = &inst[action.len_utf8()..];
+        let value = || parse_number::<i32>(arg).map_err(|e| e.at_column(2));
+        let quarter_turns = || {
+            let degrees = parse_number::<u32>(arg).map_err(|e| e.at_column(2))?;
+            if degrees.is_multiple_of(90) {
+                Ok(degrees / 90 % 4)
+            } else {
+                Err(Error::parse(format!(
+                    "expected a multiple of 90 degrees, found `{}`",
+                    arg
+                ))
+                .at_column(2))
+            }
+        };
+        match action {
+            'N' => Ok(North(value()?)),
+            'S' => Ok(South(value()?)),
+            'E' => Ok(East(value()?)),
+            'W' => Ok(West(value()?)),
+            'L' => Ok(Left(quarter_turns()?)),
+            'R' => Ok(Right(quarter_turns()?)),
+            'F' => Ok(Forward(value()?)),
+            _ => Err(Error::parse(format!("unknown action `{}`", action)).at_column(1)),
+        }
+    }
+}
+
+enum Model {
+    /// `N/S/E/W` move the ship, which turns and moves forward along its
+    /// heading, starting east.
+    Heading,
+    /// `N/S/E/W` move a waypoint relative to the ship, which the ship rotates
+    /// about itself and moves towards, starting 10 east and 1 north.
+    Waypoint,
+}
+
+/// Follows the instructions and returns the Manhattan distance from the start.
+/// Both models move the ship along a vector that `L` and `R` rotate; they
+/// differ in whether `N/S/E/W` move the ship or the vector's end.
+fn navigate(instructions: &[Instruction], model: Model) -> i32 {
+    let mut ship = (0, 0);
+    let mut vector = match model {
+        Model::Heading => (1, 0),
+        Model::Waypoint => (10, 1),
+    };
+    for instruction in instructions {
+        let (dx, dy) = match instruction {
+            North(n) => (0, *n),
+            South(n) => (0, -n),
+            East(n) => (*n, 0),
+            West(n) => (-n, 0),
+            Left(turns) => {
+                (0..*turns).for_each(|_| vector = (-vector.1, vector.0));
+                continue;
+            }
+            Right(turns) => {
+                (0..*turns).for_each(|_| vector = (vector.1, -vector.0));
+                continue;
+            }
+            Forward(n) => {
+                ship = (ship.0 + vector.0 * n, ship.1 + vector.1 * n);
+                continue;
+            }
+        };
+        let moved = match model {
+            Model::Heading => &mut ship,
+            Model::Waypoint => &mut vector,
+        };
+        *moved = (moved.0 + dx, moved.1 + dy);
+    }
+    ship.0.abs() + ship.1.abs()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day12::{navigate, Day12, Instruction, Model};
+    use crate::Day;
+
+    const TESTINPUT: &str = "F10
+N3
+F7
+R90
+F11";
+
+    #[test]
+    fn test_heading() {
+        let instructions = Day12 {}.parse(TESTINPUT).unwrap();
+        assert_eq!(navigate(&instructions, Model::Heading), 25);
+    }
+
+    #[test]
+    fn test_waypoint() {
+        let instructions = Day12 {}.parse(TESTINPUT).unwrap();
+        assert_eq!(navigate(&instructions, Model::Waypoint), 286);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Instruction::parse("L270"), Ok(Instruction::Left(3)));
+        assert_eq!(Instruction::parse("W-2"), Ok(Instruction::West(-2)));
+        assert_eq!(
+            Instruction::parse("R45").err().unwrap().to_string(),
+            "column 2: expected a multiple of 90 degrees, found `45`"
+        );
+        assert_eq!(
+            Day12 {}.parse("F10\nX3").err().unwrap().to_string(),
+            "line 2, column 1: unknown action `X`"
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use crate::error::Error;
+use crate::utils::{crt, parse_number};
+use crate::{Day, Task};
+
+pub struct Day13 {}
+
+impl Day for Day13 {
+    type Input = Notes;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Notes::parse(input)
+    }
+
+    fn get_tasks(&self) -> Vec<(usize, &Task<Self::Input>)> {
+        vec![(1, &task1), (2, &task2)]
+    }
+}
+
+fn task1(notes: &Notes) -> Result<String, Error> {
+    notes
+        .earliest_bus()
+        .map(|(id, wait)| (id * wait).to_string())
+        .ok_or_else(|| Error::no_solution("no buses are in service"))
+}
+
+fn task2(notes: &Notes) -> Result<String, Error> {
+    notes
+        .earliest_sequence()
+        .map(|t| t.to_string())
+        .ok_or_else(|| Error::no_solution("no timestamp has the buses depart in sequence"))
+}
+
+pub struct Notes {
+    earliest: u64,
+    /// Bus IDs in list order, with `None` for each `x`.
+    buses: Vec<Option<u64>>,
+}
+
+impl Notes {
+    fn parse(input: &str) -> Result<Notes, Error> {
+        let mut lines = input.lines();
+        let earliest = parse_number(lines.next().unwrap_or_default().trim())
+            .map_err(|e| e.at_line(1))?;
+        let schedule = lines
+            .next()
+            .ok_or_else(|| Error::parse("expected a bus schedule").at_line(2))?;
+        let mut column = 1;
+        let buses = schedule
+            .split(',')
+            .map(|id| {
+                let bus = match id {
+                    "x" => Ok(None),
+                    _ => match parse_number(id) {
+                        Ok(0) => Err(Error::parse("bus IDs must be positive")),
+                        id => id.map(Some),
+                    },
+                }
+                .map_err(|e| e.at_line(2).at_column(column));
+                column += id.len() + 1;
+                bus
+            })
+            .collect::<Result<_, Error>>()?;
+        Ok(Notes { earliest, buses })
+    }
+
+    /// Returns the ID of the first bus to leave at or after `earliest`, and how
+    /// long it is to wait for it.
+    fn earliest_bus(&self) -> Option<(u64, u64)> {
+        self.buses
+            .iter()
+            .flatten()
+            .map(|id| (*id, (id - self.earliest % id) % id))
+            .min_by_key(|(_, wait)| *wait)
+    }
+
+    /// Finds the first timestamp at which each bus leaves as many minutes
+    /// after it as its position in the list. Each bus contributes the
+    /// congruence `t ≡ -offset (mod id)`, which CRT combines.
+    fn earliest_sequence(&self) -> Option<u64> {
+        let congruences: Vec<(u64, u64)> = self
+            .buses
+            .iter()
+            .enumerate()
+            .filter_map(|(offset, id)| id.map(|id| ((id - offset as u64 % id) % id, id)))
+            .collect();
+        crt(&congruences).map(|(t, _)| t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day13::{Day13, Notes};
+    use crate::Day;
+
+    const TESTINPUT: &str = "939
+7,13,x,x,59,x,31,19";
+
+    #[test]
+    fn test_earliest_bus() {
+        let notes = Day13 {}.parse(TESTINPUT).unwrap();
+        assert_eq!(notes.earliest_bus(), Some((59, 5)));
+    }
+
+    #[test]
+    fn test_earliest_sequence() {
+        let sequence = |schedule: &str| {
+            Notes::parse(&format!("0\n{}", schedule))
+                .unwrap()
+                .earliest_sequence()
+        };
+        assert_eq!(
+            Day13 {}.parse(TESTINPUT).unwrap().earliest_sequence(),
+            Some(1068781)
+        );
+        assert_eq!(sequence("17,x,13,19"), Some(3417));
+        assert_eq!(sequence("67,7,59,61"), Some(754018));
+        assert_eq!(sequence("1789,37,47,1889"), Some(1202161486));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day13 {}.parse("939\n7,x,0").err().unwrap();
+        assert_eq!(err.to_string(), "line 2, column 5: bus IDs must be positive");
+    }
+}
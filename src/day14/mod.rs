@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::error::Error;
+use crate::utils::{parse_lines, parse_number};
+use crate::{Day, Task};
+
+pub struct Day14 {}
+
+impl Day for Day14 {
+    type Input = Vec<Instruction>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_lines(input.lines(), Instruction::parse)
+    }
+
+    fn get_tasks(&self) -> Vec<(usize, &Task<Self::Input>)> {
+        vec![
+            (1, &|input: &Vec<Instruction>| task(input, run_v1)),
+            (2, &|input: &Vec<Instruction>| task(input, run_v2)),
+        ]
+    }
+}
+
+fn task(
+    program: &[Instruction],
+    run: fn(&[Instruction]) -> Result<Memory, Error>,
+) -> Result<String, Error> {
+    Ok(run(program)?.values().sum::<u64>().to_string())
+}
+
+/// Addresses are 36 bits wide, so memory is only ever sparsely written.
+type Memory = HashMap<u64, u64>;
+
+const WIDTH: usize = 36;
+
+/// The most `X` bits a version 2 mask may have, as each write then touches
+/// `2^n` addresses.
+const MAX_FLOATING_BITS: u32 = 16;
+
+#[derive(Debug, PartialEq)]
+pub enum Instruction {
+    /// The mask split into the bits it sets to 1 and its `X` bits; every other
+    /// bit is 0.
+    Mask { ones: u64, floating: u64 },
+    Write { address: u64, value: u64 },
+}
+use crate::day14::Instruction::*;
+impl Instruction {
+    fn parse(inst: &str) -> Result<Instruction, Error> {
+        lazy_static! {
+            static ref MASK: Regex = Regex::new(r"^mask = ([01X]{36})$").unwrap();
+            static ref WRITE: Regex = Regex::new(r"^mem\[(\d+)\] = (\d+)$").unwrap();
+        }
+        if let Some(caps) = MASK.captures(inst) {
+            let bits = |bit: u8| {
+                caps[1]
+                    .bytes()
+                    .fold(0, |acc, b| acc << 1 | u64::from(b == bit))
+            };
+            Ok(Mask {
+                ones: bits(b'1'),
+                floating: bits(b'X'),
+            })
+        } else if let Some(caps) = WRITE.captures(inst) {
+            let address: u64 = parse_number(&caps[1])?;
+            let value: u64 = parse_number(&caps[2])?;
+            if address >> WIDTH != 0 || value >> WIDTH != 0 {
+                return Err(Error::parse(format!(
+                    "`{}` does not fit in {} bits",
+                    inst, WIDTH
+                )));
+            }
+            Ok(Write { address, value })
+        } else {
+            Err(Error::parse(format!(
+                "expected `mask = <36 bits>` or `mem[address] = value`, found `{}`",
+                inst
+            )))
+        }
+    }
+}
+
+/// The mask overwrites each value written: 1s and 0s replace its bits and
+/// `X`s leave them alone.
+fn run_v1(program: &[Instruction]) -> Result<Memory, Error> {
+    let mut memory = Memory::new();
+    let (mut ones, mut floating) = (0, (1 << WIDTH) - 1);
+    for instruction in program {
+        match instruction {
+            Mask { ones: o, floating: f } => (ones, floating) = (*o, *f),
+            Write { address, value } => {
+                memory.insert(*address, value & floating | ones);
+            }
+        }
+    }
+    Ok(memory)
+}
+
+/// The mask decodes each address instead: 1s set its bits, 0s leave them
+/// alone and `X`s float, so the value is written to every address the
+/// floating bits can make.
+fn run_v2(program: &[Instruction]) -> Result<Memory, Error> {
+    let mut memory = Memory::new();
+    let (mut ones, mut floating) = (0, 0);
+    for (line, instruction) in program.iter().enumerate() {
+        match instruction {
+            Mask { ones: o, floating: f } => {
+                if f.count_ones() > MAX_FLOATING_BITS {
+                    return Err(Error::no_solution(format!(
+                        "mask floats {} bits, more than the {} that can be expanded",
+                        f.count_ones(),
+                        MAX_FLOATING_BITS
+                    ))
+                    .at_line(line + 1));
+                }
+                (ones, floating) = (*o, *f);
+            }
+            Write { address, value } => {
+                let base = (address | ones) & !floating;
+                // Walks every subset of the floating bits, ending with none.
+                let mut subset = floating;
+                loop {
+                    memory.insert(base | subset, *value);
+                    if subset == 0 {
+                        break;
+                    }
+                    subset = (subset - 1) & floating;
+                }
+            }
+        }
+    }
+    Ok(memory)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day14::{run_v1, run_v2, task, Day14, Instruction};
+    use crate::Day;
+
+    const TEST1_INPUT: &str = "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
+mem[8] = 11
+mem[7] = 101
+mem[8] = 0";
+
+    const TEST2_INPUT: &str = "mask = 000000000000000000000000000000X1001X
+mem[42] = 100
+mask = 00000000000000000000000000000000X0XX
+mem[26] = 1";
+
+    #[test]
+    fn test_v1() {
+        let program = Day14 {}.parse(TEST1_INPUT).unwrap();
+        let memory = run_v1(&program).unwrap();
+        assert_eq!(memory.get(&7), Some(&101));
+        assert_eq!(memory.get(&8), Some(&64));
+        assert_eq!(task(&program, run_v1), Ok("165".to_string()));
+    }
+
+    #[test]
+    fn test_v2() {
+        let program = Day14 {}.parse(TEST2_INPUT).unwrap();
+        assert_eq!(run_v2(&program).unwrap().len(), 10);
+        assert_eq!(task(&program, run_v2), Ok("208".to_string()));
+    }
+
+    #[test]
+    fn test_v2_floating_limit() {
+        let program = Day14 {}.parse(TEST1_INPUT).unwrap();
+        assert_eq!(
+            run_v2(&program).err().unwrap().to_string(),
+            "line 1: mask floats 34 bits, more than the 16 that can be expanded"
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Instruction::parse("mem[3] = 5"),
+            Ok(Instruction::Write {
+                address: 3,
+                value: 5
+            })
+        );
+        assert!(Instruction::parse("mask = 1X0").is_err());
+        assert!(Instruction::parse("mem[68719476736] = 1").is_err());
+    }
+}
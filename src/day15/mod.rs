@@ -0,0 +1,93 @@
+use crate::error::Error;
+use crate::utils::parse_number;
+use crate::{Day, Task};
+
+pub struct Day15 {}
+
+impl Day for Day15 {
+    type Input = Vec<u32>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        get_input(input)
+    }
+
+    fn get_tasks(&self) -> Vec<(usize, &Task<Self::Input>)> {
+        vec![
+            (1, &|input: &Vec<u32>| task(input, 2020)),
+            (2, &|input: &Vec<u32>| task(input, 30_000_000)),
+        ]
+    }
+}
+
+fn task(starting: &[u32], turns: u32) -> Result<String, Error> {
+    play(starting, turns)
+        .map(|n| n.to_string())
+        .ok_or_else(|| Error::no_solution("no starting numbers were given"))
+}
+
+fn get_input(input: &str) -> Result<Vec<u32>, Error> {
+    let mut column = 1;
+    input
+        .trim()
+        .split(',')
+        .map(|n| {
+            let number = parse_number(n.trim()).map_err(|e| e.at_line(1).at_column(column));
+            column += n.len() + 1;
+            number
+        })
+        .collect()
+}
+
+/// Plays the game until `turns` numbers have been spoken and returns the last.
+///
+/// No spoken number can exceed the number of turns, so the turn each number
+/// was last spoken on lives in a flat table indexed by number, with 0 for
+/// never. For 30 million turns this is far quicker than a `HashMap`.
+fn play(starting: &[u32], turns: u32) -> Option<u32> {
+    let (last, earlier) = starting.split_last()?;
+    if turns as usize <= starting.len() {
+        return starting.get(turns.checked_sub(1)? as usize).copied();
+    }
+    let size = starting.iter().max().map_or(0, |m| *m as usize + 1);
+    let mut last_seen = vec![0u32; size.max(turns as usize)];
+    for (turn, n) in (1..).zip(earlier) {
+        last_seen[*n as usize] = turn;
+    }
+    let mut spoken = *last;
+    for turn in starting.len() as u32..turns {
+        let seen = std::mem::replace(&mut last_seen[spoken as usize], turn);
+        spoken = if seen == 0 { 0 } else { turn - seen };
+    }
+    Some(spoken)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day15::{play, Day15};
+    use crate::Day;
+
+    #[test]
+    fn test_play_2020() {
+        let game = |input: &str| play(&Day15 {}.parse(input).unwrap(), 2020);
+        assert_eq!(game("0,3,6"), Some(436));
+        assert_eq!(game("1,3,2"), Some(1));
+        assert_eq!(game("2,1,3"), Some(10));
+        assert_eq!(game("1,2,3"), Some(27));
+        assert_eq!(game("2,3,1"), Some(78));
+        assert_eq!(game("3,2,1"), Some(438));
+        assert_eq!(game("3,1,2"), Some(1836));
+    }
+
+    #[test]
+    fn test_play_short() {
+        assert_eq!(play(&[0, 3, 6], 3), Some(6));
+        assert_eq!(play(&[0, 3, 6], 4), Some(0));
+        assert_eq!(play(&[0, 3, 6], 10), Some(0));
+        assert_eq!(play(&[], 10), None);
+    }
+
+    #[test]
+    fn test_play_30_million() {
+        assert_eq!(play(&[0, 3, 6], 30_000_000), Some(175594));
+    }
+}
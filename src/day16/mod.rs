@@ -0,0 +1,238 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::RangeInclusive;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::error::Error;
+use crate::utils::{eliminate, parse_number};
+use crate::{Day, Task};
+
+pub struct Day16 {}
+
+impl Day for Day16 {
+    type Input = Notes;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Notes::parse(input)
+    }
+
+    fn get_tasks(&self) -> Vec<(usize, &Task<Self::Input>)> {
+        vec![(1, &task1), (2, &task2)]
+    }
+}
+
+fn task1(notes: &Notes) -> Result<String, Error> {
+    Ok(notes.error_rate().to_string())
+}
+
+fn task2(notes: &Notes) -> Result<String, Error> {
+    let columns = notes.assign_fields()?;
+    let departures: Vec<u64> = columns
+        .iter()
+        .filter(|(name, _)| name.starts_with("departure"))
+        .map(|(_, column)| u64::from(notes.yours[*column]))
+        .collect();
+    if departures.is_empty() {
+        return Err(Error::no_solution("there are no departure fields"));
+    }
+    Ok(departures.iter().product::<u64>().to_string())
+}
+
+struct Rule {
+    name: String,
+    ranges: [RangeInclusive<u32>; 2],
+}
+
+impl Rule {
+    fn parse(line: &str) -> Result<Rule, Error> {
+        lazy_static! {
+            static ref REGEX: Regex = Regex::new(r"^([^:]+): (\d+)-(\d+) or (\d+)-(\d+)$").unwrap();
+        }
+        let caps = REGEX.captures(line).ok_or_else(|| {
+            Error::parse(format!("expected `name: a-b or c-d`, found `{}`", line))
+        })?;
+        Ok(Rule {
+            name: caps[1].to_string(),
+            ranges: [
+                parse_number(&caps[2])?..=parse_number(&caps[3])?,
+                parse_number(&caps[4])?..=parse_number(&caps[5])?,
+            ],
+        })
+    }
+
+    fn allows(&self, value: u32) -> bool {
+        self.ranges.iter().any(|r| r.contains(&value))
+    }
+}
+
+type Ticket = Vec<u32>;
+
+pub struct Notes {
+    rules: Vec<Rule>,
+    yours: Ticket,
+    nearby: Vec<Ticket>,
+}
+
+impl Notes {
+    fn parse(input: &str) -> Result<Notes, Error> {
+        let mut rules = vec![];
+        let mut lines = input.lines().enumerate().map(|(i, l)| (i + 1, l)).peekable();
+        while let Some((number, line)) = lines.next_if(|(_, l)| !l.is_empty()) {
+            rules.push(Rule::parse(line).map_err(|e| e.at_line(number))?);
+        }
+        let mut section = |header: &str| -> Result<Vec<Ticket>, Error> {
+            lines.next_if(|(_, l)| l.is_empty());
+            match lines.next() {
+                Some((_, line)) if line == header => {}
+                Some((number, line)) => {
+                    return Err(Error::parse(format!(
+                        "expected `{}`, found `{}`",
+                        header, line
+                    ))
+                    .at_line(number))
+                }
+                None => return Err(Error::parse(format!("expected `{}`", header))),
+            }
+            let mut tickets = vec![];
+            while let Some((number, line)) = lines.next_if(|(_, l)| !l.is_empty()) {
+                tickets.push(parse_ticket(line, rules.len()).map_err(|e| e.at_line(number))?);
+            }
+            Ok(tickets)
+        };
+        let yours = section("your ticket:")?;
+        let nearby = section("nearby tickets:")?;
+        match <[Ticket; 1]>::try_from(yours) {
+            Ok([yours]) => Ok(Notes {
+                rules,
+                yours,
+                nearby,
+            }),
+            Err(_) => Err(Error::parse("expected exactly one ticket of your own")),
+        }
+    }
+
+    /// Sums every value on a nearby ticket that no rule allows.
+    fn error_rate(&self) -> u32 {
+        self.nearby
+            .iter()
+            .flatten()
+            .filter(|v| !self.rules.iter().any(|r| r.allows(**v)))
+            .sum()
+    }
+
+    /// Works out which column each field is in. A field can be in any column
+    /// whose values on every valid ticket its rule allows; elimination then
+    /// settles the columns one at a time.
+    fn assign_fields(&self) -> Result<BTreeMap<String, usize>, Error> {
+        let valid: Vec<&Ticket> = self
+            .nearby
+            .iter()
+            .filter(|t| t.iter().all(|v| self.rules.iter().any(|r| r.allows(*v))))
+            .chain(Some(&self.yours))
+            .collect();
+        let candidates: BTreeMap<String, BTreeSet<usize>> = self
+            .rules
+            .iter()
+            .map(|rule| {
+                let columns = (0..self.rules.len())
+                    .filter(|c| valid.iter().all(|t| rule.allows(t[*c])))
+                    .collect();
+                (rule.name.clone(), columns)
+            })
+            .collect();
+        eliminate(candidates)
+    }
+}
+
+fn parse_ticket(line: &str, fields: usize) -> Result<Ticket, Error> {
+    let mut column = 1;
+    let ticket = line
+        .split(',')
+        .map(|v| {
+            let value = parse_number(v).map_err(|e| e.at_column(column));
+            column += v.len() + 1;
+            value
+        })
+        .collect::<Result<Ticket, Error>>()?;
+    if ticket.len() != fields {
+        return Err(Error::parse(format!(
+            "expected {} values to match the rules, found {}",
+            fields,
+            ticket.len()
+        )));
+    }
+    Ok(ticket)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::day16::{task1, Day16};
+    use crate::Day;
+
+    const TEST1_INPUT: &str = "class: 1-3 or 5-7
+row: 6-11 or 33-44
+seat: 13-40 or 45-50
+
+your ticket:
+7,1,14
+
+nearby tickets:
+7,3,47
+40,4,50
+55,2,20
+38,6,12";
+
+    const TEST2_INPUT: &str = "class: 0-1 or 4-19
+row: 0-5 or 8-19
+seat: 0-13 or 16-19
+
+your ticket:
+11,12,13
+
+nearby tickets:
+3,9,18
+15,1,5
+5,14,9";
+
+    #[test]
+    fn test_error_rate() {
+        let notes = Day16 {}.parse(TEST1_INPUT).unwrap();
+        assert_eq!(task1(&notes), Ok("71".to_string()));
+    }
+
+    #[test]
+    fn test_assign_fields() {
+        let notes = Day16 {}.parse(TEST2_INPUT).unwrap();
+        let expected: BTreeMap<String, usize> = [("class", 1), ("row", 0), ("seat", 2)]
+            .iter()
+            .map(|(n, c)| (n.to_string(), *c))
+            .collect();
+        assert_eq!(notes.assign_fields(), Ok(expected));
+    }
+
+    #[test]
+    fn test_ambiguous() {
+        let input = TEST2_INPUT.replace("class: 0-1 or 4-19", "class: 0-19 or 0-19");
+        let input = input.replace("row: 0-5 or 8-19", "row: 0-19 or 0-19");
+        let notes = Day16 {}.parse(&input).unwrap();
+        assert_eq!(
+            notes.assign_fields().err().unwrap().to_string(),
+            "ambiguous constraints: `class`, `row` could each take more than one value"
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day16 {}
+            .parse(&TEST1_INPUT.replace("40,4,50", "40,4"))
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "line 10: expected 3 values to match the rules, found 2"
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::error::Error;
+use crate::grid::Grid;
+use crate::{Day, Task};
+
+pub struct Day17 {}
+
+impl Day for Day17 {
+    type Input = Grid<bool>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Grid::parse(input, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+    }
+
+    fn get_tasks(&self) -> Vec<(usize, &Task<Self::Input>)> {
+        vec![
+            (1, &|slice: &Grid<bool>| task::<3>(slice)),
+            (2, &|slice: &Grid<bool>| task::<4>(slice)),
+        ]
+    }
+}
+
+const CYCLES: usize = 6;
+
+fn task<const D: usize>(slice: &Grid<bool>) -> Result<String, Error> {
+    Ok(PocketDimension::<D>::seed(slice)
+        .run(CYCLES)
+        .active
+        .len()
+        .to_string())
+}
+
+type Cube<const D: usize> = [i32; D];
+
+/// An unbounded `D`-dimensional grid, of which only the active cubes are kept.
+struct PocketDimension<const D: usize> {
+    active: HashSet<Cube<D>>,
+}
+
+impl<const D: usize> PocketDimension<D> {
+    /// Places a 2D slice at the origin, with every other coordinate 0.
+    fn seed(slice: &Grid<bool>) -> PocketDimension<D> {
+        assert!(D >= 2, "the seed slice needs at least two dimensions");
+        let active = slice
+            .positions()
+            .filter(|(x, y)| slice.get(*x, *y) == Some(&true))
+            .map(|(x, y)| {
+                let mut cube = [0; D];
+                cube[0] = x as i32;
+                cube[1] = y as i32;
+                cube
+            })
+            .collect();
+        PocketDimension { active }
+    }
+
+    /// The `3^D - 1` steps from a cube to each of its neighbours.
+    fn offsets() -> Vec<Cube<D>> {
+        let mut offsets = vec![[0; D]];
+        for axis in 0..D {
+            offsets = offsets
+                .iter()
+                .flat_map(|o| {
+                    (-1..=1).map(move |d| {
+                        let mut o = *o;
+                        o[axis] = d;
+                        o
+                    })
+                })
+                .collect();
+        }
+        offsets.retain(|o| o.iter().any(|d| *d != 0));
+        offsets
+    }
+
+    fn run(mut self, cycles: usize) -> PocketDimension<D> {
+        let offsets = Self::offsets();
+        for _ in 0..cycles {
+            self = self.step(&offsets);
+        }
+        self
+    }
+
+    /// Active cubes stay active with 2 or 3 active neighbours and inactive
+    /// cubes turn active with exactly 3. Only cubes next to an active one
+    /// can have any, so those are all that get counted.
+    fn step(&self, offsets: &[Cube<D>]) -> PocketDimension<D> {
+        let mut neighbours: HashMap<Cube<D>, usize> = HashMap::new();
+        for cube in &self.active {
+            for offset in offsets {
+                let mut neighbour = *cube;
+                neighbour.iter_mut().zip(offset).for_each(|(c, d)| *c += d);
+                *neighbours.entry(neighbour).or_default() += 1;
+            }
+        }
+        let active = neighbours
+            .into_iter()
+            .filter(|(cube, count)| *count == 3 || (*count == 2 && self.active.contains(cube)))
+            .map(|(cube, _)| cube)
+            .collect();
+        PocketDimension { active }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day17::{task, Day17, PocketDimension};
+    use crate::Day;
+
+    const TESTINPUT: &str = ".#.
+..#
+###";
+
+    #[test]
+    fn test_offsets() {
+        assert_eq!(PocketDimension::<2>::offsets().len(), 8);
+        assert_eq!(PocketDimension::<3>::offsets().len(), 26);
+        assert_eq!(PocketDimension::<4>::offsets().len(), 80);
+    }
+
+    #[test]
+    fn test_first_cycle() {
+        let slice = Day17 {}.parse(TESTINPUT).unwrap();
+        let cubes = PocketDimension::<3>::seed(&slice).run(1);
+        assert_eq!(cubes.active.len(), 11);
+    }
+
+    #[test]
+    fn test_3d() {
+        let slice = Day17 {}.parse(TESTINPUT).unwrap();
+        assert_eq!(task::<3>(&slice), Ok("112".to_string()));
+    }
+
+    #[test]
+    fn test_4d() {
+        let slice = Day17 {}.parse(TESTINPUT).unwrap();
+        assert_eq!(task::<4>(&slice), Ok("848".to_string()));
+    }
+}
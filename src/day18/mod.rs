@@ -0,0 +1,293 @@
+use std::iter::Peekable;
+use std::slice::Iter;
+
+use crate::error::Error;
+use crate::utils::parse_lines;
+use crate::{Day, Task};
+
+pub struct Day18 {}
+
+impl Day for Day18 {
+    type Input = Vec<Vec<Token>>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_lines(input.lines(), tokenize)
+    }
+
+    fn get_tasks(&self) -> Vec<(usize, &Task<Self::Input>)> {
+        vec![
+            (1, &|input: &Vec<Vec<Token>>| task(input, &Precedence::EQUAL)),
+            (2, &|input: &Vec<Vec<Token>>| task(input, &Precedence::ADDITION_FIRST)),
+        ]
+    }
+}
+
+fn task(expressions: &[Vec<Token>], precedence: &Precedence) -> Result<String, Error> {
+    let mut sum: u64 = 0;
+    for (line, tokens) in expressions.iter().enumerate() {
+        let value = Expr::parse(tokens, precedence)
+            .and_then(|e| e.evaluate())
+            .map_err(|e| e.at_line(line + 1))?;
+        sum = sum
+            .checked_add(value)
+            .ok_or_else(|| Error::no_solution("the sum overflows"))?;
+    }
+    Ok(sum.to_string())
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Op {
+    Add,
+    Multiply,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum TokenKind {
+    Number(u64),
+    Op(Op),
+    Open,
+    Close,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Token {
+    kind: TokenKind,
+    column: usize,
+}
+
+fn tokenize(line: &str) -> Result<Vec<Token>, Error> {
+    let mut tokens: Vec<Token> = vec![];
+    for (i, c) in line.char_indices() {
+        let kind = match c {
+            ' ' => continue,
+            '+' => TokenKind::Op(Op::Add),
+            '*' => TokenKind::Op(Op::Multiply),
+            '(' => TokenKind::Open,
+            ')' => TokenKind::Close,
+            '0'..='9' => {
+                let digit = u64::from(c.to_digit(10).unwrap());
+                // Digits directly after a number extend it.
+                if line[..i].ends_with(|p: char| p.is_ascii_digit()) {
+                    if let Some(Token {
+                        kind: TokenKind::Number(n),
+                        column,
+                    }) = tokens.last_mut()
+                    {
+                        *n = n
+                            .checked_mul(10)
+                            .and_then(|n| n.checked_add(digit))
+                            .ok_or_else(|| Error::parse("number too large").at_column(*column))?;
+                        continue;
+                    }
+                }
+                TokenKind::Number(digit)
+            }
+            _ => {
+                return Err(Error::parse(format!("unexpected character `{}`", c)).at_column(i + 1))
+            }
+        };
+        tokens.push(Token {
+            kind,
+            column: i + 1,
+        });
+    }
+    Ok(tokens)
+}
+
+/// How tightly each operator binds; higher binds tighter. Operators that tie
+/// are evaluated left to right.
+pub struct Precedence {
+    add: u8,
+    multiply: u8,
+}
+
+impl Precedence {
+    const EQUAL: Precedence = Precedence {
+        add: 1,
+        multiply: 1,
+    };
+    const ADDITION_FIRST: Precedence = Precedence {
+        add: 2,
+        multiply: 1,
+    };
+
+    fn of(&self, op: Op) -> u8 {
+        match op {
+            Op::Add => self.add,
+            Op::Multiply => self.multiply,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Expr {
+    Number(u64),
+    Binary(Op, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    fn parse(tokens: &[Token], precedence: &Precedence) -> Result<Expr, Error> {
+        let mut tokens = tokens.iter().peekable();
+        let expr = Expr::parse_from(&mut tokens, precedence, 0)?;
+        match tokens.next() {
+            None => Ok(expr),
+            Some(t) => Err(Expr::leftover(t)),
+        }
+    }
+
+    /// Reports a token left over after a complete operand, which can only be
+    /// a `)` without a `(` or a missing operator.
+    fn leftover(token: &Token) -> Error {
+        match token.kind {
+            TokenKind::Close => Error::parse("unmatched `)`"),
+            _ => Error::parse("expected an operator"),
+        }
+        .at_column(token.column)
+    }
+
+    /// Pratt parsing: reads an operand, then folds in operators for as long as
+    /// they bind at least as tightly as `min_power`. Each operator's right
+    /// operand must bind strictly tighter, which keeps ties left-associative.
+    fn parse_from(
+        tokens: &mut Peekable<Iter<Token>>,
+        precedence: &Precedence,
+        min_power: u8,
+    ) -> Result<Expr, Error> {
+        let mut lhs = match tokens.next() {
+            Some(Token {
+                kind: TokenKind::Number(n),
+                ..
+            }) => Expr::Number(*n),
+            Some(Token {
+                kind: TokenKind::Open,
+                column,
+            }) => {
+                let inner = Expr::parse_from(tokens, precedence, 0)?;
+                match tokens.next() {
+                    Some(Token {
+                        kind: TokenKind::Close,
+                        ..
+                    }) => inner,
+                    Some(t) => return Err(Expr::leftover(t)),
+                    None => return Err(Error::parse("unmatched `(`").at_column(*column)),
+                }
+            }
+            Some(t) => {
+                return Err(Error::parse("expected a number or `(`").at_column(t.column));
+            }
+            None => return Err(Error::parse("unexpected end of expression")),
+        };
+        while let Some(Token {
+            kind: TokenKind::Op(op),
+            ..
+        }) = tokens.peek()
+        {
+            let power = precedence.of(*op) * 2;
+            if power < min_power {
+                break;
+            }
+            tokens.next();
+            let rhs = Expr::parse_from(tokens, precedence, power + 1)?;
+            lhs = Expr::Binary(*op, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn evaluate(&self) -> Result<u64, Error> {
+        match self {
+            Expr::Number(n) => Ok(*n),
+            Expr::Binary(op, lhs, rhs) => {
+                let (lhs, rhs) = (lhs.evaluate()?, rhs.evaluate()?);
+                match op {
+                    Op::Add => lhs.checked_add(rhs),
+                    Op::Multiply => lhs.checked_mul(rhs),
+                }
+                .ok_or_else(|| Error::no_solution("the expression overflows"))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day18::{tokenize, Expr, Op, Precedence};
+
+    fn evaluate(line: &str, precedence: &Precedence) -> u64 {
+        let tokens = tokenize(line).unwrap();
+        Expr::parse(&tokens, precedence)
+            .unwrap()
+            .evaluate()
+            .unwrap()
+    }
+
+    const EXAMPLES: [(&str, u64, u64); 6] = [
+        ("1 + 2 * 3 + 4 * 5 + 6", 71, 231),
+        ("1 + (2 * 3) + (4 * (5 + 6))", 51, 51),
+        ("2 * 3 + (4 * 5)", 26, 46),
+        ("5 + (8 * 3 + 9 + 3 * 4 * 3)", 437, 1445),
+        ("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))", 12240, 669060),
+        ("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2", 13632, 23340),
+    ];
+
+    #[test]
+    fn test_equal_precedence() {
+        for (line, expected, _) in EXAMPLES {
+            assert_eq!(evaluate(line, &Precedence::EQUAL), expected, "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_addition_first() {
+        for (line, _, expected) in EXAMPLES {
+            assert_eq!(
+                evaluate(line, &Precedence::ADDITION_FIRST),
+                expected,
+                "{}",
+                line
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_tree() {
+        use Expr::*;
+        let tokens = tokenize("12 * 3 + 4").unwrap();
+        let num = |n| Box::new(Number(n));
+        assert_eq!(
+            Expr::parse(&tokens, &Precedence::EQUAL),
+            Ok(Binary(
+                Op::Add,
+                Box::new(Binary(Op::Multiply, num(12), num(3))),
+                num(4)
+            ))
+        );
+        assert_eq!(
+            Expr::parse(&tokens, &Precedence::ADDITION_FIRST),
+            Ok(Binary(
+                Op::Multiply,
+                num(12),
+                Box::new(Binary(Op::Add, num(3), num(4)))
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |line: &str| {
+            tokenize(line)
+                .and_then(|t| Expr::parse(&t, &Precedence::EQUAL))
+                .err()
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(error("1 - 2"), "column 3: unexpected character `-`");
+        assert_eq!(error("(1 + 2"), "column 1: unmatched `(`");
+        assert_eq!(error("1 + 2)"), "column 6: unmatched `)`");
+        assert_eq!(error("1 2"), "column 3: expected an operator");
+        assert_eq!(error("(1 2)"), "column 4: expected an operator");
+        assert_eq!(error("2 (3)"), "column 3: expected an operator");
+        assert_eq!(error("1 + * 2"), "column 5: expected a number or `(`");
+        assert_eq!(error("1 +"), "unexpected end of expression");
+        assert_eq!(error("007 99999999999999999999"), "column 5: number too large");
+    }
+}
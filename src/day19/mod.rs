@@ -0,0 +1,284 @@
+use std::collections::{HashMap, HashSet};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::error::Error;
+use crate::utils::parse_number;
+use crate::{Day, Task};
+
+pub struct Day19 {}
+
+impl Day for Day19 {
+    type Input = Messages;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Messages::parse(input)
+    }
+
+    fn get_tasks(&self) -> Vec<(usize, &Task<Self::Input>)> {
+        vec![(1, &task1), (2, &task2)]
+    }
+}
+
+fn task1(messages: &Messages) -> Result<String, Error> {
+    Ok(messages.count_matching(&messages.rules).to_string())
+}
+
+fn task2(messages: &Messages) -> Result<String, Error> {
+    let mut rules = messages.rules.clone();
+    for line in ["8: 42 | 42 8", "11: 42 31 | 42 11 31"] {
+        let (number, rule) = Rule::parse(line)?;
+        rules.insert(number, rule);
+    }
+    check_references(&rules)?;
+    Ok(messages.count_matching(&rules).to_string())
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Rule {
+    Literal(u8),
+    /// Any one of several sequences of other rules.
+    Alternatives(Vec<Vec<usize>>),
+}
+
+impl Rule {
+    fn parse(line: &str) -> Result<(usize, Rule), Error> {
+        lazy_static! {
+            static ref RULE: Regex = Regex::new(r#"^(\d+): (?:"([[:alnum:]])"|([\d |]+))$"#).unwrap();
+        }
+        let caps = RULE.captures(line).ok_or_else(|| {
+            Error::parse(format!(
+                "expected `n: \"c\"` or `n: a b | c d`, found `{}`",
+                line
+            ))
+        })?;
+        let number = parse_number(&caps[1])?;
+        if let Some(literal) = caps.get(2) {
+            return Ok((number, Rule::Literal(literal.as_str().as_bytes()[0])));
+        }
+        let alternatives = caps[3]
+            .split('|')
+            .map(|sequence| {
+                let sequence: Vec<usize> = sequence
+                    .split_ascii_whitespace()
+                    .map(parse_number)
+                    .collect::<Result<_, Error>>()?;
+                if sequence.is_empty() {
+                    return Err(Error::parse(format!("empty alternative in `{}`", line)));
+                }
+                Ok(sequence)
+            })
+            .collect::<Result<_, Error>>()?;
+        Ok((number, Rule::Alternatives(alternatives)))
+    }
+}
+
+type Rules = HashMap<usize, Rule>;
+
+fn check_references(rules: &Rules) -> Result<(), Error> {
+    if !rules.contains_key(&0) {
+        return Err(Error::parse("there is no rule 0"));
+    }
+    for rule in rules.values() {
+        if let Rule::Alternatives(alternatives) = rule {
+            if let Some(missing) = alternatives.iter().flatten().find(|r| !rules.contains_key(r)) {
+                return Err(Error::parse(format!("rule {} is not defined", missing)));
+            }
+        }
+    }
+    // A rule that leads back to itself through the first rule of a sequence
+    // would be matched again at the same position, without end.
+    let mut numbers: Vec<&usize> = rules.keys().collect();
+    numbers.sort_unstable();
+    for start in numbers {
+        let mut stack = vec![*start];
+        let mut seen = HashSet::new();
+        while let Some(rule) = stack.pop() {
+            if let Rule::Alternatives(alternatives) = &rules[&rule] {
+                for first in alternatives.iter().map(|s| s[0]) {
+                    if first == *start {
+                        return Err(Error::parse(format!(
+                            "rule {} can reach itself without consuming a character",
+                            start
+                        )));
+                    }
+                    if seen.insert(first) {
+                        stack.push(first);
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+pub struct Messages {
+    rules: Rules,
+    messages: Vec<String>,
+}
+
+impl Messages {
+    fn parse(input: &str) -> Result<Messages, Error> {
+        let mut lines = input.lines().enumerate();
+        let mut rules = Rules::new();
+        for (i, line) in lines.by_ref().take_while(|(_, l)| !l.is_empty()) {
+            let (number, rule) = Rule::parse(line).map_err(|e| e.at_line(i + 1))?;
+            if rules.insert(number, rule).is_some() {
+                return Err(Error::parse(format!("rule {} is defined twice", number)).at_line(i + 1));
+            }
+        }
+        check_references(&rules)?;
+        let messages = lines.map(|(_, l)| l.to_string()).collect();
+        Ok(Messages { rules, messages })
+    }
+
+    fn count_matching(&self, rules: &Rules) -> usize {
+        self.messages
+            .iter()
+            .filter(|m| match_ends(rules, 0, m.as_bytes(), 0).contains(&m.len()))
+            .count()
+    }
+}
+
+/// Returns every position `rule` can match up to when starting at `start`.
+/// Following all of them, rather than the first, lets a rule like
+/// `8: 42 | 42 8` try each number of repetitions. [`check_references`] makes
+/// sure a rule consumes a character before it can recurse into itself, so
+/// recursive rules always run out of input.
+fn match_ends(rules: &Rules, rule: usize, message: &[u8], start: usize) -> Vec<usize> {
+    if start >= message.len() {
+        return vec![];
+    }
+    match &rules[&rule] {
+        Rule::Literal(c) => {
+            if message[start] == *c {
+                vec![start + 1]
+            } else {
+                vec![]
+            }
+        }
+        Rule::Alternatives(alternatives) => {
+            let mut ends: Vec<usize> = alternatives
+                .iter()
+                .flat_map(|sequence| {
+                    sequence.iter().fold(vec![start], |positions, r| {
+                        positions
+                            .into_iter()
+                            .flat_map(|p| match_ends(rules, *r, message, p))
+                            .collect()
+                    })
+                })
+                .collect();
+            ends.sort_unstable();
+            ends.dedup();
+            ends
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day19::{match_ends, task1, task2, Day19};
+    use crate::Day;
+
+    const TEST1_INPUT: &str = r#"0: 4 1 5
+1: 2 3 | 3 2
+2: 4 4 | 5 5
+3: 4 5 | 5 4
+4: "a"
+5: "b"
+
+ababbb
+bababa
+abbbab
+aaabbb
+aaaabbb"#;
+
+    const TEST2_INPUT: &str = r#"42: 9 14 | 10 1
+9: 14 27 | 1 26
+10: 23 14 | 28 1
+1: "a"
+11: 42 31
+5: 1 14 | 15 1
+19: 14 1 | 14 14
+12: 24 14 | 19 1
+16: 15 1 | 14 14
+31: 14 17 | 1 13
+6: 14 14 | 1 14
+2: 1 24 | 14 4
+0: 8 11
+13: 14 3 | 1 12
+15: 1 | 14
+17: 14 2 | 1 7
+23: 25 1 | 22 14
+28: 16 1
+4: 1 1
+20: 14 14 | 1 15
+3: 5 14 | 16 1
+27: 1 6 | 14 18
+14: "b"
+21: 14 1 | 1 14
+25: 1 1 | 1 14
+22: 14 14
+8: 42
+26: 14 22 | 1 20
+18: 15 15
+7: 14 5 | 1 21
+24: 14 1
+
+abbbbbabbbaaaababbaabbbbabababbbabbbbbbabaaaa
+bbabbbbaabaabba
+babbbbaabbbbbabbbbbbaabaaabaaa
+aaabbbbbbaaaabaababaabababbabaaabbababababaaa
+bbbbbbbaaaabbbbaaabbabaaa
+bbbababbbbaaaaaaaabbababaaababaabab
+ababaaaaaabaaab
+ababaaaaabbbaba
+baabbaaaabbaaaababbaababb
+abbbbabbbbaaaababbbbbbaaaababb
+aaaaabbaabaaaaababaa
+aaaabbaaaabbaaa
+aaaabbaabbaaaaaaabbbabbbaaabbaabaaa
+babaaabbbaaabaababbaabababaaab
+aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba"#;
+
+    #[test]
+    fn test_match() {
+        let messages = Day19 {}.parse(TEST1_INPUT).unwrap();
+        assert_eq!(task1(&messages), Ok("2".to_string()));
+        assert_eq!(match_ends(&messages.rules, 0, b"aaaabbb", 0), vec![6]);
+    }
+
+    #[test]
+    fn test_recursive_rules() {
+        let messages = Day19 {}.parse(TEST2_INPUT).unwrap();
+        assert_eq!(task1(&messages), Ok("3".to_string()));
+        assert_eq!(task2(&messages), Ok("12".to_string()));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |input: &str| Day19 {}.parse(input).err().unwrap().to_string();
+        assert_eq!(error("0: 1 2\n1: \"a\"\n\na"), "rule 2 is not defined");
+        assert_eq!(
+            error("0: 1\n1: \"a\"\n1: \"b\"\n"),
+            "line 3: rule 1 is defined twice"
+        );
+        assert!(error("0: 1 |\n1: \"a\"").starts_with("line 1: "));
+    }
+
+    #[test]
+    fn test_left_recursion() {
+        let error = |input: &str| Day19 {}.parse(input).err().unwrap().to_string();
+        assert_eq!(
+            error("0: 8\n8: 8 1 | 1\n1: \"a\"\n\na"),
+            "rule 8 can reach itself without consuming a character"
+        );
+        assert_eq!(
+            error("0: 1\n1: 2\n2: 1\n\na"),
+            "rule 1 can reach itself without consuming a character"
+        );
+        assert!(Day19 {}.parse("0: 1 0 | 1\n1: \"a\"\n\na").is_ok());
+    }
+}
@@ -1,40 +1,45 @@
 use lazy_static::lazy_static;
 use regex::Regex;
 
-use crate::Day;
+use crate::error::Error;
+use crate::utils::{parse_lines, parse_number};
+use crate::{Day, Task};
 
 pub struct Day2 {}
 
-impl<'a> Day<'a> for Day2 {
-    fn get_tasks(&self) -> Vec<(usize, &dyn Fn() -> String)> {
+impl Day for Day2 {
+    type Input = Vec<PasswordAndPolicy>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        get_input(input)
+    }
+
+    fn get_tasks(&self) -> Vec<(usize, &Task<Self::Input>)> {
         vec![
-            (1, &|| task(PolicyType::Part1)),
-            (2, &|| task(PolicyType::Part2)),
+            (1, &|input: &Vec<PasswordAndPolicy>| task(input, PolicyType::Part1)),
+            (2, &|input: &Vec<PasswordAndPolicy>| task(input, PolicyType::Part2)),
         ]
     }
 
-    fn get_day_number(&self) -> usize {
-        2
+    fn get_embedded_input(&self) -> Option<&'static str> {
+        Some(INPUT)
     }
 }
 
-fn task(policy: PolicyType) -> String {
-    get_input()
+fn task(input: &[PasswordAndPolicy], policy: PolicyType) -> Result<String, Error> {
+    Ok(input
         .iter()
         .filter(|p| p.is_valid(&policy))
         .count()
-        .to_string()
+        .to_string())
 }
 
-fn get_input() -> Vec<PasswordAndPolicy> {
-    INPUT
-        .lines()
-        .map(|s| PasswordAndPolicy::from_string(&s.to_string()))
-        .collect()
+fn get_input(input: &str) -> Result<Vec<PasswordAndPolicy>, Error> {
+    parse_lines(input.lines(), PasswordAndPolicy::from_string)
 }
 
 #[derive(Debug, PartialEq)]
-struct PasswordAndPolicy {
+pub struct PasswordAndPolicy {
     password: String,
     min: usize,
     max: usize,
@@ -54,22 +59,34 @@ impl PasswordAndPolicy {
                 count >= self.min && count <= self.max
             }
             PolicyType::Part2 => {
-                (self.password.chars().nth(self.min - 1).unwrap() == self.char)
-                    != (self.password.chars().nth(self.max - 1).unwrap() == self.char)
+                (self.password.chars().nth(self.min - 1) == Some(self.char))
+                    != (self.password.chars().nth(self.max - 1) == Some(self.char))
             }
         }
     }
-    fn from_string(input: &String) -> PasswordAndPolicy {
+    fn from_string(input: &str) -> Result<PasswordAndPolicy, Error> {
         lazy_static! {
             static ref REGEX: Regex = Regex::new(r"^(\d+)-(\d+) (\w): (\w+)").unwrap();
         }
-        let caps = REGEX.captures(input).unwrap();
-        PasswordAndPolicy {
-            min: caps.get(1).unwrap().as_str().parse::<usize>().unwrap(),
-            max: caps.get(2).unwrap().as_str().parse::<usize>().unwrap(),
-            char: caps.get(3).unwrap().as_str().chars().next().unwrap(),
-            password: caps.get(4).unwrap().as_str().to_string(),
+        let caps = REGEX.captures(input).ok_or_else(|| {
+            Error::parse(format!(
+                "expected `min-max char: password`, found `{}`",
+                input
+            ))
+        })?;
+        let policy = PasswordAndPolicy {
+            min: parse_number(&caps[1])?,
+            max: parse_number(&caps[2])?,
+            char: caps[3].chars().next().unwrap_or_default(),
+            password: caps[4].to_string(),
+        };
+        if policy.min == 0 || policy.max < policy.min {
+            return Err(Error::parse(format!(
+                "invalid policy range `{}-{}`",
+                policy.min, policy.max
+            )));
         }
+        Ok(policy)
     }
 }
 
@@ -81,82 +98,66 @@ mod tests {
 
     #[test]
     fn test_is_valid_part1() {
-        assert_eq!(
-            day2::PasswordAndPolicy {
-                password: "abcde".to_string(),
-                min: 1,
-                max: 3,
-                char: 'a'
-            }
-            .is_valid(&PolicyType::Part1),
-            true
-        );
-        assert_eq!(
-            day2::PasswordAndPolicy {
-                password: "cdefg".to_string(),
-                min: 1,
-                max: 3,
-                char: 'b'
-            }
-            .is_valid(&PolicyType::Part1),
-            false
-        );
-        assert_eq!(
-            day2::PasswordAndPolicy {
-                password: "ccccccccc".to_string(),
-                min: 2,
-                max: 9,
-                char: 'c'
-            }
-            .is_valid(&PolicyType::Part1),
-            true
-        );
+        assert!(day2::PasswordAndPolicy {
+            password: "abcde".to_string(),
+            min: 1,
+            max: 3,
+            char: 'a'
+        }
+        .is_valid(&PolicyType::Part1));
+        assert!(!day2::PasswordAndPolicy {
+            password: "cdefg".to_string(),
+            min: 1,
+            max: 3,
+            char: 'b'
+        }
+        .is_valid(&PolicyType::Part1));
+        assert!(day2::PasswordAndPolicy {
+            password: "ccccccccc".to_string(),
+            min: 2,
+            max: 9,
+            char: 'c'
+        }
+        .is_valid(&PolicyType::Part1));
     }
 
     #[test]
     fn test_is_valid_part2() {
-        assert_eq!(
-            day2::PasswordAndPolicy {
-                password: "abcde".to_string(),
-                min: 1,
-                max: 3,
-                char: 'a'
-            }
-            .is_valid(&PolicyType::Part2),
-            true
-        );
-        assert_eq!(
-            day2::PasswordAndPolicy {
-                password: "cdefg".to_string(),
-                min: 1,
-                max: 3,
-                char: 'b'
-            }
-            .is_valid(&PolicyType::Part2),
-            false
-        );
-        assert_eq!(
-            day2::PasswordAndPolicy {
-                password: "ccccccccc".to_string(),
-                min: 2,
-                max: 9,
-                char: 'c'
-            }
-            .is_valid(&PolicyType::Part2),
-            false
-        );
+        assert!(day2::PasswordAndPolicy {
+            password: "abcde".to_string(),
+            min: 1,
+            max: 3,
+            char: 'a'
+        }
+        .is_valid(&PolicyType::Part2));
+        assert!(!day2::PasswordAndPolicy {
+            password: "cdefg".to_string(),
+            min: 1,
+            max: 3,
+            char: 'b'
+        }
+        .is_valid(&PolicyType::Part2));
+        assert!(!day2::PasswordAndPolicy {
+            password: "ccccccccc".to_string(),
+            min: 2,
+            max: 9,
+            char: 'c'
+        }
+        .is_valid(&PolicyType::Part2));
     }
 
     #[test]
     fn test_from_string() {
         assert_eq!(
-            PasswordAndPolicy::from_string(&"1-3 a: abcde".to_string()),
-            day2::PasswordAndPolicy {
+            PasswordAndPolicy::from_string("1-3 a: abcde"),
+            Ok(day2::PasswordAndPolicy {
                 password: "abcde".to_string(),
                 min: 1,
                 max: 3,
                 char: 'a'
-            }
+            })
         );
+        assert!(PasswordAndPolicy::from_string("1-3 a abcde").is_err());
+        assert!(PasswordAndPolicy::from_string("0-3 a: abcde").is_err());
     }
 }
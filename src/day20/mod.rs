@@ -0,0 +1,309 @@
+use std::collections::{HashMap, HashSet};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::error::Error;
+use crate::grid::Grid;
+use crate::utils::parse_number;
+use crate::{Day, Task};
+
+pub struct Day20 {}
+
+impl Day for Day20 {
+    type Input = Vec<Tile>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_tiles(input)
+    }
+
+    fn get_tasks(&self) -> Vec<(usize, &Task<Self::Input>)> {
+        vec![
+            (1, &|tiles: &Vec<Tile>| task1(tiles)),
+            (2, &|tiles: &Vec<Tile>| task2(tiles)),
+        ]
+    }
+}
+
+fn task1(tiles: &[Tile]) -> Result<String, Error> {
+    let corners = find_corners(tiles);
+    if corners.len() != 4 {
+        return Err(Error::no_solution(format!(
+            "expected 4 corner tiles, found {}",
+            corners.len()
+        )));
+    }
+    Ok(corners.iter().map(|t| t.id).product::<u64>().to_string())
+}
+
+fn task2(tiles: &[Tile]) -> Result<String, Error> {
+    let image = assemble(tiles)?;
+    water_roughness(&image)
+        .map(|r| r.to_string())
+        .ok_or_else(|| Error::no_solution("no orientation of the image has sea monsters"))
+}
+
+pub struct Tile {
+    id: u64,
+    image: Grid<bool>,
+}
+
+impl Tile {
+    /// The edges read clockwise: top, right, bottom and left.
+    fn edges(image: &Grid<bool>) -> [Vec<bool>; 4] {
+        let last = image.width() - 1;
+        [
+            image.row(0).to_vec(),
+            image.column(last).copied().collect(),
+            image.row(image.height() - 1).iter().rev().copied().collect(),
+            image.column(0).rev().copied().collect(),
+        ]
+    }
+}
+
+/// An edge as read either way round, so that it is the same whichever way up
+/// its tile is placed.
+fn canonical(edge: &[bool]) -> Vec<bool> {
+    let reversed: Vec<bool> = edge.iter().rev().copied().collect();
+    reversed.min(edge.to_vec())
+}
+
+fn parse_pixel(c: char) -> Option<bool> {
+    match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_tiles(input: &str) -> Result<Vec<Tile>, Error> {
+    lazy_static! {
+        static ref HEADER: Regex = Regex::new(r"^Tile (\d+):$").unwrap();
+    }
+    let mut tiles: Vec<Tile> = vec![];
+    let mut lines = input.lines().enumerate().map(|(i, l)| (i + 1, l)).peekable();
+    loop {
+        while lines.next_if(|(_, l)| l.is_empty()).is_some() {}
+        let (line, header) = match lines.next() {
+            Some(header) => header,
+            None => break,
+        };
+        let caps = HEADER.captures(header).ok_or_else(|| {
+            Error::parse(format!("expected `Tile <id>:`, found `{}`", header)).at_line(line)
+        })?;
+        let id = parse_number(&caps[1]).map_err(|e| e.at_line(line))?;
+        let mut rows = vec![];
+        while let Some((_, row)) = lines.next_if(|(_, l)| !l.is_empty()) {
+            rows.push(row);
+        }
+        let image = Grid::parse(&rows.join("\n"), parse_pixel).map_err(|e| match e.line {
+            Some(l) => e.at_line(line + l),
+            None => e.at_line(line),
+        })?;
+        if image.width() != image.height() || image.width() < 3 {
+            return Err(Error::parse(format!("tile {} is not a square image", id)).at_line(line));
+        }
+        if tiles.iter().any(|t| t.image.width() != image.width()) {
+            return Err(Error::parse(format!("tile {} is not the same size as the others", id))
+                .at_line(line));
+        }
+        tiles.push(Tile { id, image });
+    }
+    Ok(tiles)
+}
+
+/// Counts how many tiles have each edge.
+fn edge_counts(tiles: &[Tile]) -> HashMap<Vec<bool>, usize> {
+    let mut counts = HashMap::new();
+    for tile in tiles {
+        for edge in Tile::edges(&tile.image) {
+            *counts.entry(canonical(&edge)).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+/// Corner tiles are the ones with two neighbouring edges that match no other
+/// tile, and whose other two edges do.
+fn find_corners(tiles: &[Tile]) -> Vec<&Tile> {
+    let counts = edge_counts(tiles);
+    tiles
+        .iter()
+        .filter(|t| {
+            let outer = Tile::edges(&t.image).map(|e| counts[&canonical(&e)] == 1);
+            outer.iter().filter(|o| **o).count() == 2
+                && (0..4).any(|i| outer[i] && outer[(i + 1) % 4])
+        })
+        .collect()
+}
+
+/// Lays the tiles out in a square, starting from a corner turned so its
+/// unmatched edges face out, then fitting each next tile against its left (or
+/// for the first column, upper) neighbour. Returns the picture with each
+/// tile's border stripped.
+fn assemble(tiles: &[Tile]) -> Result<Grid<bool>, Error> {
+    let side = (1..=tiles.len())
+        .find(|s| s * s >= tiles.len())
+        .filter(|s| s * s == tiles.len())
+        .ok_or_else(|| Error::no_solution("the tiles do not make a square"))?;
+    let counts = edge_counts(tiles);
+    let is_outer = |edge: &Vec<bool>| counts[&canonical(edge)] == 1;
+    let corner = find_corners(tiles)
+        .first()
+        .copied()
+        .ok_or_else(|| Error::no_solution("there are no corner tiles"))?;
+    let start = corner
+        .image
+        .orientations()
+        .into_iter()
+        .find(|o| {
+            let [top, _, _, left] = Tile::edges(o);
+            is_outer(&top) && is_outer(&left)
+        })
+        .ok_or_else(|| Error::no_solution("the corner tile cannot be turned to fit"))?;
+
+    let mut used: HashSet<u64> = HashSet::from([corner.id]);
+    let mut placed: Vec<Grid<bool>> = vec![start];
+    for i in 1..tiles.len() {
+        // The next tile's top must match the bottom of the tile above it when
+        // it starts a row, and otherwise its left must match its neighbour's right.
+        let starts_row = i % side == 0;
+        let neighbour = &placed[if starts_row { i - side } else { i - 1 }];
+        let edge: Vec<bool> = if starts_row {
+            neighbour.row(neighbour.height() - 1).to_vec()
+        } else {
+            neighbour.column(neighbour.width() - 1).copied().collect()
+        };
+        let fits = |o: &Grid<bool>| {
+            if starts_row {
+                o.row(0) == edge.as_slice()
+            } else {
+                o.column(0).copied().eq(edge.iter().copied())
+            }
+        };
+        let (id, image) = tiles
+            .iter()
+            .filter(|t| !used.contains(&t.id))
+            .flat_map(|t| t.image.orientations().into_iter().map(move |o| (t.id, o)))
+            .find(|(_, o)| fits(o))
+            .ok_or_else(|| {
+                Error::no_solution(format!(
+                    "no tile fits at row {}, column {}",
+                    i / side + 1,
+                    i % side + 1
+                ))
+            })?;
+        used.insert(id);
+        placed.push(image);
+    }
+
+    let inner = placed[0].width() - 2;
+    let stripped: Vec<Grid<bool>> = placed.iter().map(|t| t.crop(1, 1, inner, inner)).collect();
+    let width = side * inner;
+    let cells = (0..width)
+        .flat_map(|y| {
+            let row = &stripped[y / inner * side..(y / inner + 1) * side];
+            row.iter().flat_map(move |t| t.row(y % inner).iter().copied())
+        })
+        .collect();
+    Ok(Grid::from_cells(width, cells))
+}
+
+const SEA_MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
+
+/// Finds the orientation of the image with sea monsters in it and counts the
+/// rough water: the `#`s that are not part of any sea monster.
+fn water_roughness(image: &Grid<bool>) -> Option<usize> {
+    let monster: Vec<(usize, usize)> = SEA_MONSTER
+        .iter()
+        .enumerate()
+        .flat_map(|(y, l)| l.match_indices('#').map(move |(x, _)| (x, y)))
+        .collect();
+    let (width, height) = (SEA_MONSTER[0].len(), SEA_MONSTER.len());
+    image.orientations().iter().find_map(|image| {
+        let mut monster_cells = HashSet::new();
+        for y in 0..=image.height().saturating_sub(height) {
+            for x in 0..=image.width().saturating_sub(width) {
+                let cells: Vec<(usize, usize)> =
+                    monster.iter().map(|(dx, dy)| (x + dx, y + dy)).collect();
+                if cells.iter().all(|(x, y)| image.get(*x, *y) == Some(&true)) {
+                    monster_cells.extend(cells);
+                }
+            }
+        }
+        (!monster_cells.is_empty())
+            .then(|| image.cells().iter().filter(|c| **c).count() - monster_cells.len())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day20::{assemble, find_corners, task1, task2, Day20};
+    use crate::Day;
+
+    const TESTINPUT: &str = include_str!("example.txt");
+
+    #[test]
+    fn test_corners() {
+        let tiles = Day20 {}.parse(TESTINPUT).unwrap();
+        assert_eq!(tiles.len(), 9);
+        assert_eq!(task1(&tiles), Ok("20899048083289".to_string()));
+    }
+
+    #[test]
+    fn test_assemble() {
+        let tiles = Day20 {}.parse(TESTINPUT).unwrap();
+        let image = assemble(&tiles).unwrap();
+        assert_eq!(image.width(), 24);
+        assert_eq!(image.height(), 24);
+    }
+
+    #[test]
+    fn test_water_roughness() {
+        let tiles = Day20 {}.parse(TESTINPUT).unwrap();
+        assert_eq!(task2(&tiles), Ok("273".to_string()));
+    }
+
+    #[test]
+    fn test_no_corners() {
+        // A single row of tiles: the middle two have unmatched top and bottom
+        // edges, which does not make them corners.
+        let tiles = Day20 {}
+            .parse(
+                "Tile 1:\n.####\n....#\n#..##\n..###\n...##\n
+Tile 2:\n#...#\n#.#.#\n#.#.#\n###..\n#.#.#\n
+Tile 3:\n#..#.\n#..#.\n#..#.\n..#.#\n#....\n
+Tile 4:\n.###.\n.###.\n.#.##\n#.##.\n.#.##",
+            )
+            .unwrap();
+        assert!(find_corners(&tiles).is_empty());
+        assert_eq!(
+            assemble(&tiles).err().unwrap().to_string(),
+            "there are no corner tiles"
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day20 {}
+            .parse("Tile 1:\n#.#\n...\n#.#\n\nTile 2:\n#.#\n.x.\n#.#")
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "line 8, column 2: unexpected character `x`");
+        let err = Day20 {}
+            .parse("Tile 1:\r\n#.#\r\n...\r\n#.#\r\n\r\n\r\nTile 2:\r\n#.#\r\n.x.\r\n#.#\r\n")
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "line 9, column 2: unexpected character `x`");
+    }
+
+    #[test]
+    fn test_crlf() {
+        let tiles = Day20 {}.parse(&TESTINPUT.replace('\n', "\r\n")).unwrap();
+        assert_eq!(task1(&tiles), Ok("20899048083289".to_string()));
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::error::Error;
+use crate::utils::{eliminate, intersect_all, parse_lines};
+use crate::{Day, Task};
+
+pub struct Day21 {}
+
+impl Day for Day21 {
+    type Input = Vec<Food>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_lines(input.lines(), Food::parse)
+    }
+
+    fn get_tasks(&self) -> Vec<(usize, &Task<Self::Input>)> {
+        vec![
+            (1, &|foods: &Vec<Food>| task1(foods)),
+            (2, &|foods: &Vec<Food>| task2(foods)),
+        ]
+    }
+}
+
+fn task1(foods: &[Food]) -> Result<String, Error> {
+    let suspects: BTreeSet<&String> = candidates(foods).into_values().flatten().collect();
+    Ok(foods
+        .iter()
+        .flat_map(|f| &f.ingredients)
+        .filter(|i| !suspects.contains(i))
+        .count()
+        .to_string())
+}
+
+fn task2(foods: &[Food]) -> Result<String, Error> {
+    let dangerous = eliminate(candidates(foods))?;
+    Ok(dangerous
+        .into_values()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(","))
+}
+
+pub struct Food {
+    ingredients: BTreeSet<String>,
+    allergens: Vec<String>,
+}
+
+impl Food {
+    fn parse(line: &str) -> Result<Food, Error> {
+        lazy_static! {
+            static ref REGEX: Regex =
+                Regex::new(r"^(\w+(?: \w+)*)(?: \(contains (\w+(?:, \w+)*)\))?$").unwrap();
+        }
+        let caps = REGEX.captures(line).ok_or_else(|| {
+            Error::parse(format!(
+                "expected `ingredient... (contains allergen, ...)`, found `{}`",
+                line
+            ))
+        })?;
+        Ok(Food {
+            ingredients: caps[1].split(' ').map(String::from).collect(),
+            allergens: caps
+                .get(2)
+                .map_or(vec![], |a| a.as_str().split(", ").map(String::from).collect()),
+        })
+    }
+}
+
+/// Each allergen is in exactly one ingredient, which must then be in every
+/// food listing that allergen: the candidates are the intersection of their
+/// ingredients.
+fn candidates(foods: &[Food]) -> BTreeMap<&String, BTreeSet<&String>> {
+    let allergens: BTreeSet<&String> = foods.iter().flat_map(|f| &f.allergens).collect();
+    allergens
+        .into_iter()
+        .map(|allergen| {
+            let listed = foods
+                .iter()
+                .filter(|f| f.allergens.contains(allergen))
+                .map(|f| f.ingredients.iter().collect());
+            let candidates = intersect_all(listed).expect("the allergen is listed somewhere");
+            (allergen, candidates)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day21::{task1, task2, Day21};
+    use crate::Day;
+
+    const TESTINPUT: &str = "mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
+trh fvjkl sbzzf mxmxvkd (contains dairy)
+sqjhc fvjkl (contains soy)
+sqjhc mxmxvkd sbzzf (contains fish)";
+
+    #[test]
+    fn test_safe_ingredients() {
+        let foods = Day21 {}.parse(TESTINPUT).unwrap();
+        assert_eq!(task1(&foods), Ok("5".to_string()));
+    }
+
+    #[test]
+    fn test_dangerous_ingredients() {
+        let foods = Day21 {}.parse(TESTINPUT).unwrap();
+        assert_eq!(task2(&foods), Ok("mxmxvkd,sqjhc,fvjkl".to_string()));
+    }
+
+    #[test]
+    fn test_parse() {
+        let foods = Day21 {}.parse("a b\nc (contains x)").unwrap();
+        assert!(foods[0].allergens.is_empty());
+        assert_eq!(foods[1].allergens, vec!["x".to_string()]);
+        let err = Day21 {}.parse("a (contains)").err().unwrap();
+        assert!(err.to_string().starts_with("line 1: expected"));
+    }
+}
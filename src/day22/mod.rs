@@ -0,0 +1,220 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::error::Error;
+use crate::utils::parse_number;
+use crate::{Day, Task};
+
+pub struct Day22 {}
+
+impl Day for Day22 {
+    type Input = Decks;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Decks::parse(input)
+    }
+
+    fn get_tasks(&self) -> Vec<(usize, &Task<Self::Input>)> {
+        vec![(1, &task1), (2, &task2)]
+    }
+}
+
+fn task1(decks: &Decks) -> Result<String, Error> {
+    let (_, deck) = combat(decks.clone())
+        .ok_or_else(|| Error::no_solution("the game of Combat repeats forever"))?;
+    Ok(score(&deck).to_string())
+}
+
+fn task2(decks: &Decks) -> Result<String, Error> {
+    let (_, deck) = recursive_combat(decks.clone(), false);
+    Ok(score(&deck).to_string())
+}
+
+type Deck = VecDeque<u8>;
+
+#[derive(Clone)]
+pub struct Decks {
+    player1: Deck,
+    player2: Deck,
+}
+
+#[derive(Debug, PartialEq)]
+enum Winner {
+    Player1,
+    Player2,
+}
+
+impl Decks {
+    fn parse(input: &str) -> Result<Decks, Error> {
+        let mut decks = vec![];
+        let mut lines = input.lines().enumerate().map(|(i, l)| (i + 1, l)).peekable();
+        loop {
+            while lines.next_if(|(_, l)| l.is_empty()).is_some() {}
+            let (line, header) = match lines.next() {
+                Some(header) => header,
+                None => break,
+            };
+            let expected = format!("Player {}:", decks.len() + 1);
+            if header != expected || decks.len() > 1 {
+                return Err(Error::parse(format!(
+                    "expected `{}`, found `{}`",
+                    if decks.len() > 1 { "end of input" } else { &expected },
+                    header
+                ))
+                .at_line(line));
+            }
+            let mut deck = Deck::new();
+            while let Some((number, card)) = lines.next_if(|(_, l)| !l.is_empty()) {
+                deck.push_back(parse_number(card).map_err(|e| e.at_line(number))?);
+            }
+            decks.push(deck);
+        }
+        match <[Deck; 2]>::try_from(decks) {
+            Ok([player1, player2]) => Ok(Decks { player1, player2 }),
+            Err(_) => Err(Error::parse("expected a deck for each of two players")),
+        }
+    }
+}
+
+/// Both players draw and the higher card wins the round, going to the bottom
+/// of the winner's deck above the loser's card, until one deck is empty.
+/// Nothing stops the decks cycling back to an earlier position, in which case
+/// the game never ends and there is no winner.
+fn combat(mut decks: Decks) -> Option<(Winner, Deck)> {
+    let mut seen: HashSet<(Deck, Deck)> = HashSet::new();
+    while !decks.player1.is_empty() && !decks.player2.is_empty() {
+        if !seen.insert((decks.player1.clone(), decks.player2.clone())) {
+            return None;
+        }
+        let (a, b) = (
+            decks.player1.pop_front().unwrap(),
+            decks.player2.pop_front().unwrap(),
+        );
+        if a > b {
+            decks.player1.extend([a, b]);
+        } else {
+            decks.player2.extend([b, a]);
+        }
+    }
+    Some(winner(decks))
+}
+
+/// Like [`combat`], except that a round where both players hold at least as
+/// many cards as they drew is won by playing a sub-game on copies of that many
+/// cards, and any repeat of an earlier position in the same game is won by
+/// player 1 on the spot.
+fn recursive_combat(mut decks: Decks, sub_game: bool) -> (Winner, Deck) {
+    // Nobody can take the highest card from player 1, and a sub-game ends in
+    // player 1's favour at the latest when it repeats, so there is no need to
+    // play it out. This doesn't hold for the top-level game, whose winning deck
+    // is needed for the score.
+    if sub_game && decks.player1.iter().max() > decks.player2.iter().max() {
+        return (Winner::Player1, decks.player1);
+    }
+    let mut seen: HashSet<(Deck, Deck)> = HashSet::new();
+    while !decks.player1.is_empty() && !decks.player2.is_empty() {
+        if !seen.insert((decks.player1.clone(), decks.player2.clone())) {
+            return (Winner::Player1, decks.player1);
+        }
+        let (a, b) = (
+            decks.player1.pop_front().unwrap(),
+            decks.player2.pop_front().unwrap(),
+        );
+        let round = if decks.player1.len() >= a as usize && decks.player2.len() >= b as usize {
+            let sub_decks = Decks {
+                player1: decks.player1.iter().take(a as usize).copied().collect(),
+                player2: decks.player2.iter().take(b as usize).copied().collect(),
+            };
+            recursive_combat(sub_decks, true).0
+        } else if a > b {
+            Winner::Player1
+        } else {
+            Winner::Player2
+        };
+        match round {
+            Winner::Player1 => decks.player1.extend([a, b]),
+            Winner::Player2 => decks.player2.extend([b, a]),
+        }
+    }
+    winner(decks)
+}
+
+fn winner(decks: Decks) -> (Winner, Deck) {
+    if decks.player2.is_empty() {
+        (Winner::Player1, decks.player1)
+    } else {
+        (Winner::Player2, decks.player2)
+    }
+}
+
+/// Each card scores its value times its position counted from the bottom.
+fn score(deck: &Deck) -> u64 {
+    deck.iter()
+        .rev()
+        .zip(1..)
+        .map(|(card, position)| u64::from(*card) * position)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day22::{combat, recursive_combat, task1, task2, Day22, Winner};
+    use crate::Day;
+
+    const TESTINPUT: &str = "Player 1:
+9
+2
+6
+3
+1
+
+Player 2:
+5
+8
+4
+7
+10";
+
+    #[test]
+    fn test_combat() {
+        let decks = Day22 {}.parse(TESTINPUT).unwrap();
+        assert_eq!(combat(decks.clone()).map(|r| r.0), Some(Winner::Player2));
+        assert_eq!(task1(&decks), Ok("306".to_string()));
+    }
+
+    #[test]
+    fn test_recursive_combat() {
+        let decks = Day22 {}.parse(TESTINPUT).unwrap();
+        assert_eq!(task2(&decks), Ok("291".to_string()));
+    }
+
+    #[test]
+    fn test_repeated_state() {
+        let decks = Day22 {}
+            .parse("Player 1:\n43\n19\n\nPlayer 2:\n2\n29\n14\n")
+            .unwrap();
+        assert!(combat(decks.clone()).is_none());
+        assert_eq!(recursive_combat(decks, false).0, Winner::Player1);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day22 {}
+            .parse("Player 1:\n9\n\nPlayer 2:\n5\nx")
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "line 6: invalid number `x`");
+        assert!(Day22 {}.parse("Player 1:\n9").is_err());
+        assert!(Day22 {}.parse("Player 1:\n9\n\nPlayer 2:\n8\n\n").is_ok());
+        let err = Day22 {}
+            .parse("Player 1:\r\n9\r\n\r\n\r\nPlayer 2:\r\ny\r\n")
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "line 6: invalid number `y`");
+    }
+
+    #[test]
+    fn test_crlf() {
+        let decks = Day22 {}.parse(&TESTINPUT.replace('\n', "\r\n")).unwrap();
+        assert_eq!(task2(&decks), Ok("291".to_string()));
+    }
+}
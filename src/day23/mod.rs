@@ -0,0 +1,166 @@
+use crate::error::Error;
+use crate::{Day, Task};
+
+pub struct Day23 {}
+
+impl Day for Day23 {
+    type Input = Vec<u32>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        get_input(input)
+    }
+
+    fn get_tasks(&self) -> Vec<(usize, &Task<Self::Input>)> {
+        vec![
+            (1, &|labels: &Vec<u32>| task1(labels)),
+            (2, &|labels: &Vec<u32>| task2(labels)),
+        ]
+    }
+}
+
+fn task1(labels: &[u32]) -> Result<String, Error> {
+    let mut cups = Cups::new(labels, labels.len());
+    cups.play(100);
+    Ok(cups
+        .after_one()
+        .take(labels.len() - 1)
+        .map(|c| c.to_string())
+        .collect())
+}
+
+fn task2(labels: &[u32]) -> Result<String, Error> {
+    let mut cups = Cups::new(labels, 1_000_000);
+    cups.play(10_000_000);
+    Ok(cups
+        .after_one()
+        .take(2)
+        .map(u64::from)
+        .product::<u64>()
+        .to_string())
+}
+
+/// Reads the starting cups, which must be labelled 1 to however many there
+/// are, in any order.
+fn get_input(input: &str) -> Result<Vec<u32>, Error> {
+    let line = input.trim();
+    let labels: Vec<u32> = line
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            c.to_digit(10).filter(|d| *d > 0).ok_or_else(|| {
+                Error::parse(format!("expected a cup label, found `{}`", c))
+                    .at_line(1)
+                    .at_column(i + 1)
+            })
+        })
+        .collect::<Result<_, Error>>()?;
+    if labels.len() < 4 {
+        return Err(Error::parse("a move picks up three cups, so there must be at least four"));
+    }
+    let mut sorted = labels.clone();
+    sorted.sort_unstable();
+    if !sorted.iter().copied().eq(1..=labels.len() as u32) {
+        return Err(Error::parse(format!(
+            "expected each of the labels 1 to {} once, found `{}`",
+            labels.len(),
+            line
+        )));
+    }
+    Ok(labels)
+}
+
+/// The circle of cups as a successor table: `next[label]` is the label of
+/// the cup clockwise of it (index 0 is unused). Moving three cups is then a
+/// matter of relinking three entries, however many cups there are.
+struct Cups {
+    next: Vec<u32>,
+    current: u32,
+}
+
+impl Cups {
+    /// Places `labels` in order, followed by cups labelled upwards from the
+    /// highest of them until there are `total` cups.
+    fn new(labels: &[u32], total: usize) -> Cups {
+        let order: Vec<u32> = labels
+            .iter()
+            .copied()
+            .chain(labels.len() as u32 + 1..=total as u32)
+            .collect();
+        let mut next = vec![0; order.len() + 1];
+        for (cup, after) in order.iter().zip(order.iter().cycle().skip(1)) {
+            next[*cup as usize] = *after;
+        }
+        Cups {
+            next,
+            current: order[0],
+        }
+    }
+
+    fn play(&mut self, moves: usize) {
+        let highest = (self.next.len() - 1) as u32;
+        for _ in 0..moves {
+            let first = self.next[self.current as usize];
+            let second = self.next[first as usize];
+            let third = self.next[second as usize];
+            let mut destination = self.current;
+            loop {
+                destination = if destination == 1 {
+                    highest
+                } else {
+                    destination - 1
+                };
+                if destination != first && destination != second && destination != third {
+                    break;
+                }
+            }
+            self.next[self.current as usize] = self.next[third as usize];
+            self.next[third as usize] = self.next[destination as usize];
+            self.next[destination as usize] = first;
+            self.current = self.next[self.current as usize];
+        }
+    }
+
+    /// The labels clockwise from cup 1, not including it.
+    fn after_one(&self) -> impl Iterator<Item = u32> + '_ {
+        std::iter::successors(Some(self.next[1]), move |c| Some(self.next[*c as usize]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day23::{task1, task2, Cups, Day23};
+    use crate::Day;
+
+    const TESTINPUT: &str = "389125467";
+
+    #[test]
+    fn test_ten_moves() {
+        let labels = Day23 {}.parse(TESTINPUT).unwrap();
+        let mut cups = Cups::new(&labels, labels.len());
+        cups.play(10);
+        let order: Vec<u32> = cups.after_one().take(8).collect();
+        assert_eq!(order, vec![9, 2, 6, 5, 8, 3, 7, 4]);
+    }
+
+    #[test]
+    fn test_hundred_moves() {
+        let labels = Day23 {}.parse(TESTINPUT).unwrap();
+        assert_eq!(task1(&labels), Ok("67384529".to_string()));
+    }
+
+    #[test]
+    fn test_million_cups() {
+        let labels = Day23 {}.parse(TESTINPUT).unwrap();
+        assert_eq!(task2(&labels), Ok("149245887792".to_string()));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            Day23 {}.parse("3a1").err().unwrap().to_string(),
+            "line 1, column 2: expected a cup label, found `a`"
+        );
+        assert!(Day23 {}.parse("1224").is_err());
+        assert!(Day23 {}.parse("312").is_err());
+    }
+}
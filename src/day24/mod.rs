@@ -0,0 +1,141 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::error::Error;
+use crate::hex::{Hex, HexDirection};
+use crate::utils::parse_lines;
+use crate::{Day, Task};
+
+pub struct Day24 {}
+
+impl Day for Day24 {
+    type Input = Vec<Vec<HexDirection>>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_lines(input.lines(), parse_path)
+    }
+
+    fn get_tasks(&self) -> Vec<(usize, &Task<Self::Input>)> {
+        vec![
+            (1, &|paths: &Vec<Vec<HexDirection>>| task1(paths)),
+            (2, &|paths: &Vec<Vec<HexDirection>>| task2(paths)),
+        ]
+    }
+}
+
+fn task1(paths: &[Vec<HexDirection>]) -> Result<String, Error> {
+    Ok(lay_tiles(paths).len().to_string())
+}
+
+fn task2(paths: &[Vec<HexDirection>]) -> Result<String, Error> {
+    let black = (0..100).fold(lay_tiles(paths), |black, _| next_day(&black));
+    Ok(black.len().to_string())
+}
+
+/// Reads a path of directions written without separators, such as `esenee`.
+fn parse_path(line: &str) -> Result<Vec<HexDirection>, Error> {
+    let mut path = vec![];
+    let mut rest = line;
+    while !rest.is_empty() {
+        let column = line.len() - rest.len() + 1;
+        let (direction, len) = match rest.as_bytes() {
+            [b'e', ..] => (HexDirection::East, 1),
+            [b'w', ..] => (HexDirection::West, 1),
+            [b's', b'e', ..] => (HexDirection::SouthEast, 2),
+            [b's', b'w', ..] => (HexDirection::SouthWest, 2),
+            [b'n', b'e', ..] => (HexDirection::NorthEast, 2),
+            [b'n', b'w', ..] => (HexDirection::NorthWest, 2),
+            _ => {
+                let found: String = rest.chars().take(2).collect();
+                return Err(
+                    Error::parse(format!("expected a direction, found `{}`", found))
+                        .at_column(column),
+                );
+            }
+        };
+        path.push(direction);
+        rest = &rest[len..];
+    }
+    Ok(path)
+}
+
+/// Flips the tile at the end of each path, starting from the same reference
+/// tile every time, and returns the tiles left black.
+fn lay_tiles(paths: &[Vec<HexDirection>]) -> HashSet<Hex> {
+    let mut black = HashSet::new();
+    for path in paths {
+        let tile = path.iter().fold(Hex::default(), |h, d| h.step(*d));
+        if !black.remove(&tile) {
+            black.insert(tile);
+        }
+    }
+    black
+}
+
+/// Black tiles with no black neighbours or more than two turn white, and
+/// white tiles with exactly two black neighbours turn black.
+fn next_day(black: &HashSet<Hex>) -> HashSet<Hex> {
+    let mut neighbours: HashMap<Hex, usize> = HashMap::new();
+    for tile in black {
+        for neighbour in tile.neighbours() {
+            *neighbours.entry(neighbour).or_default() += 1;
+        }
+    }
+    neighbours
+        .into_iter()
+        .filter(|(tile, count)| *count == 2 || (*count == 1 && black.contains(tile)))
+        .map(|(tile, _)| tile)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day24::{lay_tiles, next_day, task1, task2, Day24};
+    use crate::hex::Hex;
+    use crate::Day;
+
+    const TESTINPUT: &str = "sesenwnenenewseeswwswswwnenewsewsw
+neeenesenwnwwswnenewnwwsewnenwseswesw
+seswneswswsenwwnwse
+nwnwneseeswswnenewneswwnewseswneseene
+swweswneswnenwsewnwneneseenw
+eesenwseswswnenwswnwnwsewwnwsene
+sewnenenenesenwsewnenwwwse
+wenwwweseeeweswwwnwwe
+wsweesenenewnwwnwsenewsenwwsesesenwne
+neeswseenwwswnwswswnw
+nenwswwsewswnenenewsenwsenwnesesenew
+enewnwewneswsewnwswenweswnenwsenwsw
+sweneswneswneneenwnewenewwneswswnese
+swwesenesewenwneswnwwneseswwne
+enesenwswwswneneswsenwnewswseenwsese
+wnwnesenesenenwwnenwsewesewsesesew
+nenewswnwewswnenesenwnesewesw
+eneswnwswnwsenenwnwnwwseeswneewsenese
+neswnwewnwnwseenwseesewsenwsweewe
+wseweeenwnesenwwwswnew";
+
+    #[test]
+    fn test_lay_tiles() {
+        let paths = Day24 {}.parse(TESTINPUT).unwrap();
+        assert_eq!(task1(&paths), Ok("10".to_string()));
+        let single = Day24 {}.parse("nwwswee").unwrap();
+        assert!(lay_tiles(&single).contains(&Hex::default()));
+    }
+
+    #[test]
+    fn test_days() {
+        let paths = Day24 {}.parse(TESTINPUT).unwrap();
+        let day1 = next_day(&lay_tiles(&paths));
+        assert_eq!(day1.len(), 15);
+        assert_eq!(task2(&paths), Ok("2208".to_string()));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day24 {}.parse("esew\nnwsx").err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: expected a direction, found `sx`"
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use crate::error::Error;
+use crate::utils::{discrete_log, parse_lines, parse_number, pow_mod};
+use crate::{Day, Task};
+
+pub struct Day25 {}
+
+impl Day for Day25 {
+    type Input = PublicKeys;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        PublicKeys::parse(input)
+    }
+
+    fn get_tasks(&self) -> Vec<(usize, &Task<Self::Input>)> {
+        vec![(1, &task1)]
+    }
+}
+
+fn task1(keys: &PublicKeys) -> Result<String, Error> {
+    let loop_size = loop_size(keys.card)
+        .ok_or_else(|| Error::no_solution("no loop size gives the card's public key"))?;
+    Ok(transform(keys.door, loop_size).to_string())
+}
+
+const MODULUS: u64 = 20201227;
+const SUBJECT: u64 = 7;
+
+pub struct PublicKeys {
+    card: u64,
+    door: u64,
+}
+
+impl PublicKeys {
+    fn parse(input: &str) -> Result<PublicKeys, Error> {
+        let keys: Vec<u64> = parse_lines(input.lines(), |l| {
+            let key = parse_number(l.trim())?;
+            if key >= MODULUS {
+                return Err(Error::parse(format!(
+                    "public keys are below {}, found {}",
+                    MODULUS, key
+                )));
+            }
+            Ok(key)
+        })?;
+        match keys[..] {
+            [card, door] => Ok(PublicKeys { card, door }),
+            _ => Err(Error::parse(format!(
+                "expected the card's and the door's public keys, found {} lines",
+                keys.len()
+            ))),
+        }
+    }
+}
+
+/// Transforming the subject number loops `value = value * subject % MODULUS`
+/// starting from 1, so after `loop_size` loops it is `subject^loop_size`.
+fn transform(subject: u64, loop_size: u64) -> u64 {
+    pow_mod(subject, loop_size, MODULUS)
+}
+
+/// Recovers the secret loop size from a public key, a discrete logarithm
+/// that baby-step giant-step finds without running the loop up to millions
+/// of times.
+fn loop_size(public_key: u64) -> Option<u64> {
+    discrete_log(SUBJECT, public_key, MODULUS)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day25::{loop_size, task1, transform, Day25, SUBJECT};
+    use crate::Day;
+
+    const TESTINPUT: &str = "5764801
+17807724";
+
+    #[test]
+    fn test_loop_size() {
+        assert_eq!(loop_size(5764801), Some(8));
+        assert_eq!(loop_size(17807724), Some(11));
+        for secret in [0, 1, 4_493, 10_000_000, 20_201_225] {
+            let key = transform(SUBJECT, secret);
+            assert_eq!(loop_size(key), Some(secret), "{}", key);
+        }
+    }
+
+    #[test]
+    fn test_encryption_key() {
+        assert_eq!(transform(17807724, 8), 14897079);
+        assert_eq!(transform(5764801, 11), 14897079);
+        let keys = Day25 {}.parse(TESTINPUT).unwrap();
+        assert_eq!(task1(&keys), Ok("14897079".to_string()));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert!(Day25 {}.parse("5764801").is_err());
+        assert_eq!(
+            Day25 {}.parse("1\n20201227").err().unwrap().to_string(),
+            "line 2: public keys are below 20201227, found 20201227"
+        );
+    }
+}
@@ -1,36 +1,39 @@
-use crate::Day;
+use crate::error::Error;
+use crate::grid;
+use crate::{Day, Task};
 
 pub struct Day3 {}
 
-impl<'a> Day<'a> for Day3 {
-    fn get_tasks(&self) -> Vec<(usize, &dyn Fn() -> String)> {
-        vec![(1, &|| task1()), (2, &|| task2())]
+impl Day for Day3 {
+    type Input = Grid;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Grid::parse(input)
     }
 
-    fn get_day_number(&self) -> usize {
-        3
+    fn get_tasks(&self) -> Vec<(usize, &Task<Self::Input>)> {
+        vec![(1, &task1), (2, &task2)]
     }
-}
 
-fn get_grid() -> Grid {
-    let input: Vec<&str> = INPUT.lines().collect();
-    Grid::parse(&input)
+    fn get_embedded_input(&self) -> Option<&'static str> {
+        Some(INPUT)
+    }
 }
 
-fn task1() -> String {
-    get_grid()
+fn task1(grid: &Grid) -> Result<String, Error> {
+    Ok(grid
         .run_grid_and_count_trees(&Movement { right: 3, down: 1 })
-        .to_string()
+        .to_string())
 }
 
-fn task2() -> String {
+fn task2(grid: &Grid) -> Result<String, Error> {
     let part2_movements = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
     let res2: usize = part2_movements
         .iter()
-        .map(|i| Movement::from_tuple(i))
-        .map(|m| get_grid().run_grid_and_count_trees(&m))
+        .map(Movement::from_tuple)
+        .map(|m| grid.run_grid_and_count_trees(&m))
         .product();
-    res2.to_string()
+    Ok(res2.to_string())
 }
 
 struct Position {
@@ -52,8 +55,8 @@ impl Movement {
     }
 }
 
-struct Grid {
-    grid: Vec<Vec<bool>>,
+pub struct Grid {
+    grid: grid::Grid<bool>,
 }
 
 impl Grid {
@@ -73,23 +76,24 @@ impl Grid {
     }
 
     fn is_tree(&self, pos: &Position) -> bool {
-        self.grid[pos.y][pos.x]
+        self.grid.get(pos.x, pos.y) == Some(&true)
     }
 
-    fn parse(lines: &Vec<&str>) -> Grid {
-        let grid: Vec<Vec<bool>> = lines
-            .iter()
-            .map(|l| l.chars().map(|c| c == '#').collect())
-            .collect();
-        Grid { grid }
+    fn parse(input: &str) -> Result<Grid, Error> {
+        let grid = grid::Grid::parse(input, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
+        Ok(Grid { grid })
     }
 }
 
 impl Position {
     fn next_position(&self, movement: &Movement, grid: &Grid) -> Option<Position> {
         let maybe_y = self.y + movement.down;
-        grid.grid.get(maybe_y).map(|x_grid| Position {
-            x: (self.x + movement.right) % x_grid.len(),
+        (maybe_y < grid.grid.height()).then(|| Position {
+            x: (self.x + movement.right) % grid.grid.width(),
             y: maybe_y,
         })
     }
@@ -103,7 +107,8 @@ const INPUT: &str = include_str!("input.txt");
 
 #[cfg(test)]
 mod tests {
-    use crate::day3::{Grid, Movement};
+    use crate::day3::{task2, Day3, Grid, Movement};
+    use crate::Day;
 
     const TESTINPUT: &str = "..##.........##.........##.........##.........##.........##.......
 #...#...#..#...#...#..#...#...#..#...#...#..#...#...#..#...#...#..
@@ -119,10 +124,21 @@ mod tests {
 
     #[test]
     fn test_case() {
-        let input: Vec<&str> = TESTINPUT.lines().collect();
-        let grid = Grid::parse(&input);
+        let grid = Grid::parse(TESTINPUT).unwrap();
         let movement = Movement { right: 3, down: 1 };
         let count = grid.run_grid_and_count_trees(&movement);
         assert_eq!(count, 7);
     }
+
+    #[test]
+    fn test_task_2() {
+        let grid = Day3 {}.parse(TESTINPUT).unwrap();
+        assert_eq!(task2(&grid), Ok("336".to_string()));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Grid::parse("..#\n.x.").err().unwrap();
+        assert_eq!(err.to_string(), "line 2, column 2: unexpected character `x`");
+    }
 }
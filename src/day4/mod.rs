@@ -1,30 +1,40 @@
 use regex::Regex;
 
-use crate::Day;
+use crate::error::Error;
+use crate::{Day, Task};
 
 pub struct Day4 {}
 
-impl<'a> Day<'a> for Day4 {
-    fn get_tasks(&self) -> Vec<(usize, &dyn Fn() -> String)> {
-        vec![(1, &|| task(false)), (2, &|| task(true))]
+impl Day for Day4 {
+    type Input = Vec<Document>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(get_input(input))
+    }
+
+    fn get_tasks(&self) -> Vec<(usize, &Task<Self::Input>)> {
+        vec![
+            (1, &|input: &Vec<Document>| task(input, false)),
+            (2, &|input: &Vec<Document>| task(input, true)),
+        ]
     }
 
-    fn get_day_number(&self) -> usize {
-        4
+    fn get_embedded_input(&self) -> Option<&'static str> {
+        Some(INPUT)
     }
 }
 
-fn task(strict: bool) -> String {
+fn task(input: &[Document], strict: bool) -> Result<String, Error> {
     let passport = get_passport();
-    get_input()
+    Ok(input
         .iter()
         .filter(|d| d.is_passport(&passport, strict))
         .count()
-        .to_string()
+        .to_string())
 }
 
-fn get_input() -> Vec<Document> {
-    input_to_documents(INPUT)
+fn get_input(input: &str) -> Vec<Document> {
+    input_to_documents(input)
 }
 
 const INPUT: &str = include_str!("input.txt");
@@ -36,14 +46,13 @@ fn input_to_documents(input: &str) -> Vec<Document> {
             fields: d
                 .to_string()
                 .lines()
-                .map(|l| l.split_ascii_whitespace().map(|w| w.to_string()))
-                .flatten()
+                .flat_map(|l| l.split_ascii_whitespace().map(|w| w.to_string()))
                 .collect(),
         })
         .collect()
 }
 
-struct Document {
+pub struct Document {
     fields: Vec<String>,
 }
 
@@ -51,12 +60,12 @@ struct DocumentType {
     required_fields: Vec<DocumentField>,
 }
 
-impl<'a> DocumentField {
+impl DocumentField {
     fn is_valid(&self, doc: &str, strict: bool) -> bool {
         if !doc.contains(&format!("{}:", self.id)) {
-            return false;
+            false
         } else {
-            return !strict || self.valid_by_rule(doc);
+            !strict || self.valid_by_rule(doc)
         }
     }
 
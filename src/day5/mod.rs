@@ -1,36 +1,50 @@
-use crate::Day;
+use crate::error::Error;
+use crate::utils::parse_lines;
+use crate::{Day, Task};
 pub struct Day5 {}
 
-impl<'a> Day<'a> for Day5 {
-    fn get_tasks(&self) -> Vec<(usize, &dyn Fn() -> String)> {
-        vec![(1, &task1), (2, &task2)]
+impl Day for Day5 {
+    type Input = Vec<u32>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        get_ticket_ids(input)
+    }
+
+    fn get_tasks(&self) -> Vec<(usize, &Task<Self::Input>)> {
+        vec![
+            (1, &|ids: &Vec<u32>| task1(ids)),
+            (2, &|ids: &Vec<u32>| task2(ids)),
+        ]
     }
 
-    fn get_day_number(&self) -> usize {
-        5
+    fn get_embedded_input(&self) -> Option<&'static str> {
+        Some(INPUT)
     }
 }
 
-fn task1() -> String {
-    get_ticket_ids().max().unwrap().to_string()
+fn task1(ids: &[u32]) -> Result<String, Error> {
+    ids.iter()
+        .max()
+        .map(|id| id.to_string())
+        .ok_or_else(|| Error::no_solution("no boarding passes"))
 }
 
-fn task2() -> String {
-    let mut ids = get_ticket_ids().collect::<Vec<u32>>();
+fn task2(ids: &[u32]) -> Result<String, Error> {
+    let mut ids = ids.to_vec();
     ids.sort();
     let mut prev: Option<u32> = None;
     for id in ids {
         if id > 1 && prev == Some(id - 2) {
-            return (id - 1).to_string();
+            return Ok((id - 1).to_string());
         } else {
             prev = Some(id)
         }
     }
-    "ERROR".to_string()
+    Err(Error::no_solution("no free seat between two taken seats"))
 }
 
-fn get_ticket_ids() -> Box<dyn Iterator<Item = u32>> {
-    Box::new(INPUT.lines().map(|p| Pass::decode_pass(p).get_id()))
+fn get_ticket_ids(input: &str) -> Result<Vec<u32>, Error> {
+    parse_lines(input.lines(), |p| Pass::decode_pass(p).map(|p| p.get_id()))
 }
 
 const INPUT: &str = include_str!("input.txt");
@@ -48,22 +62,31 @@ impl PartialEq for Pass {
 }
 
 impl Pass {
-    fn decode_pass(code: &str) -> Pass {
-        Pass {
+    fn decode_pass(code: &str) -> Result<Pass, Error> {
+        if let Some((i, c)) = code.chars().enumerate().find(|(i, c)| match i {
+            0..=6 => !matches!(c, 'F' | 'B'),
+            _ => !matches!(c, 'L' | 'R'),
+        }) {
+            let expected = if i < 7 { "`F` or `B`" } else { "`L` or `R`" };
+            return Err(
+                Error::parse(format!("expected {}, found `{}`", expected, c)).at_column(i + 1),
+            );
+        }
+        if code.len() != 10 {
+            return Err(Error::parse(format!(
+                "expected 10 characters, found {}",
+                code.chars().count()
+            )));
+        }
+        Ok(Pass {
             row: Pass::sub_pass(&code[0..7], 'B'),
             column: Pass::sub_pass(&code[7..10], 'R'),
-        }
+        })
     }
 
     fn sub_pass(code: &str, upper_char: char) -> u32 {
-        u32::from_str_radix(
-            code.chars()
-                .map(|b| if b == upper_char { '1' } else { '0' })
-                .collect::<String>()
-                .as_str(),
-            2,
-        )
-        .unwrap()
+        code.chars()
+            .fold(0, |acc, b| (acc << 1) | u32::from(b == upper_char))
     }
 
     fn get_id(&self) -> u32 {
@@ -85,7 +108,12 @@ mod tests {
     fn test_decode_pass() {
         assert_eq!(
             day5::Pass::decode_pass("FBFBBFFRLR"),
-            Pass { row: 44, column: 5 }
+            Ok(Pass { row: 44, column: 5 })
+        );
+        assert!(day5::Pass::decode_pass("FBFBBFFRL").is_err());
+        assert_eq!(
+            day5::Pass::decode_pass("FBFBBFFRXR").err().unwrap().column,
+            Some(9)
         );
     }
 }
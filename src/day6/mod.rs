@@ -1,22 +1,32 @@
-use crate::Day;
-use std::collections::HashSet;
-use std::iter::FromIterator;
+use crate::error::Error;
+use crate::utils::intersect_all;
+use crate::{Day, Task};
 use std::str::Split;
 
 pub struct Day6 {}
 
-impl<'a> Day<'a> for Day6 {
-    fn get_tasks(&self) -> Vec<(usize, &dyn Fn() -> String)> {
-        vec![(1, &task1), (2, &task2)]
+impl Day for Day6 {
+    type Input = Vec<String>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Ok(get_input(input).map(String::from).collect())
+    }
+
+    fn get_tasks(&self) -> Vec<(usize, &Task<Self::Input>)> {
+        vec![
+            (1, &|groups: &Vec<String>| task1(groups)),
+            (2, &|groups: &Vec<String>| task2(groups)),
+        ]
     }
 
-    fn get_day_number(&self) -> usize {
-        6
+    fn get_embedded_input(&self) -> Option<&'static str> {
+        Some(INPUT)
     }
 }
 
-fn task1() -> String {
-    get_input()
+fn task1(groups: &[String]) -> Result<String, Error> {
+    Ok(groups
+        .iter()
         .map(|f| {
             let mut qs = f.replace("\n", "").chars().collect::<Vec<char>>();
             qs.sort();
@@ -24,27 +34,22 @@ fn task1() -> String {
             qs.len()
         })
         .sum::<usize>()
-        .to_string()
+        .to_string())
 }
 
-fn task2() -> String {
-    get_input()
+fn task2(groups: &[String]) -> Result<String, Error> {
+    Ok(groups
+        .iter()
         .map(|f| {
-            f.lines()
-                .map(|x| HashSet::from_iter(x.chars().into_iter()))
-                .reduce(|a: HashSet<char>, b| {
-                    let r: HashSet<&char> = HashSet::from_iter(a.intersection(&b));
-                    HashSet::from_iter(r.iter().map(|x| *x.to_owned()))
-                })
-                .unwrap()
-                .len()
+            intersect_all(f.lines().map(|x| x.chars().collect()))
+                .map_or(0, |s| s.len())
         })
         .sum::<usize>()
-        .to_string()
+        .to_string())
 }
 
-fn get_input<'a>() -> Split<'a, &'a str> {
-    INPUT.split("\n\n")
+fn get_input(input: &str) -> Split<'_, &str> {
+    input.split("\n\n")
 }
 
 const INPUT: &str = include_str!("input.txt");
@@ -1,4 +1,6 @@
-use crate::Day;
+use crate::error::Error;
+use crate::utils::{parse_lines, parse_number};
+use crate::{Day, Task};
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::collections::HashMap;
@@ -6,65 +8,63 @@ use std::str::Lines;
 
 pub struct Day7 {}
 
-impl<'a> Day<'a> for Day7 {
-    fn get_tasks(&self) -> Vec<(usize, &dyn Fn() -> String)> {
+impl Day for Day7 {
+    type Input = HashMap<String, Bag>;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        parse_input(input.lines())
+    }
+
+    fn get_tasks(&self) -> Vec<(usize, &Task<Self::Input>)> {
         vec![(1, &task1), (2, &task2)]
     }
 
-    fn get_day_number(&self) -> usize {
-        7
+    fn get_embedded_input(&self) -> Option<&'static str> {
+        Some(INPUT)
     }
 }
 
-fn task1() -> String {
-    let parsed = parse_input(INPUT.lines());
+fn task1(parsed: &HashMap<String, Bag>) -> Result<String, Error> {
     let mut memo = HashMap::new();
-    parsed
+    Ok(parsed
         .values()
-        .filter(|b| b.can_contain("shiny gold", &parsed, &mut memo))
+        .filter(|b| b.can_contain("shiny gold", parsed, &mut memo))
         .count()
-        .to_string()
+        .to_string())
 }
 
-fn task2() -> String {
-    let parsed = parse_input(INPUT.lines());
+fn task2(parsed: &HashMap<String, Bag>) -> Result<String, Error> {
     let mut memo = HashMap::new();
-    parsed
+    Ok(parsed
         .get("shiny gold")
-        .unwrap()
-        .contains(&parsed, &mut memo)
-        .to_string()
+        .ok_or_else(|| Error::no_solution("no rule for shiny gold bags"))?
+        .contains(parsed, &mut memo)
+        .to_string())
 }
 
-struct Bag {
+pub struct Bag {
     color: String,
     contains: HashMap<String, u32>,
 }
 
 impl Bag {
-    fn parse(input: &str) -> Bag {
+    fn parse(input: &str) -> Result<Bag, Error> {
         lazy_static! {
             static ref THIS_BAG_RE: Regex = Regex::new(r"^([a-z]+ [a-z]+)").unwrap();
             static ref CONTAINS_BAGS_RE: Regex = Regex::new(r"(\d+) ([a-z]+ [a-z]+)").unwrap();
         }
         let this_bag = THIS_BAG_RE
             .captures(input)
-            .unwrap()
-            .get(1)
-            .unwrap()
-            .as_str()
+            .ok_or_else(|| Error::parse(format!("expected a bag colour, found `{}`", input)))?[1]
             .to_string();
         let mut contains_bags: HashMap<String, u32> = HashMap::new();
-        CONTAINS_BAGS_RE.captures_iter(input).for_each(|m| {
-            contains_bags.insert(
-                m.get(2).unwrap().as_str().to_string(),
-                m.get(1).unwrap().as_str().parse().unwrap(),
-            );
-        });
-        Bag {
+        for m in CONTAINS_BAGS_RE.captures_iter(input) {
+            contains_bags.insert(m[2].to_string(), parse_number(&m[1])?);
+        }
+        Ok(Bag {
             color: this_bag,
             contains: contains_bags,
-        }
+        })
     }
 
     fn can_contain(
@@ -78,7 +78,7 @@ impl Bag {
                 || match memo.get(b) {
                     Some(con) => con.to_owned(),
                     None => {
-                        let con = rules.get(b).unwrap().can_contain(name, rules, memo);
+                        let con = rules[b].can_contain(name, rules, memo);
                         memo.insert(b.to_string(), con);
                         con
                     }
@@ -93,7 +93,7 @@ impl Bag {
                 c + c * match memo.get(b) {
                     Some(cnt) => *cnt,
                     None => {
-                        let cnt = rules.get(b).unwrap().contains(rules, memo);
+                        let cnt = rules[b].contains(rules, memo);
                         memo.insert(b.to_string(), cnt);
                         cnt
                     }
@@ -103,13 +103,19 @@ impl Bag {
     }
 }
 
-fn parse_input(input: Lines) -> HashMap<String, Bag> {
-    input
-        .map(|l| {
-            let bag = Bag::parse(l);
-            (bag.color.clone(), bag)
-        })
-        .collect()
+/// Parses the rules, checking every contained bag has a rule of its own so
+/// the traversals can look them up directly.
+fn parse_input(input: Lines) -> Result<HashMap<String, Bag>, Error> {
+    let bags = parse_lines(input, Bag::parse)?;
+    let colors: Vec<&str> = bags.iter().map(|b| b.color.as_str()).collect();
+    for (i, bag) in bags.iter().enumerate() {
+        if let Some(missing) = bag.contains.keys().find(|c| !colors.contains(&c.as_str())) {
+            return Err(
+                Error::parse(format!("no rule for contained bag `{}`", missing)).at_line(i + 1),
+            );
+        }
+    }
+    Ok(bags.into_iter().map(|b| (b.color.clone(), b)).collect())
 }
 
 const INPUT: &str = include_str!("input.txt");
@@ -132,13 +138,14 @@ dotted black bags contain no other bags.";
 
     #[test]
     fn test_parse() {
-        let parsed = parse_input(TEST1_INPUT.lines());
+        let parsed = parse_input(TEST1_INPUT.lines()).unwrap();
         assert_eq!(parsed.len(), 9);
+        assert!(parse_input("shiny gold bags contain 2 dark red bags.".lines()).is_err());
     }
 
     #[test]
     fn test_can_contain() {
-        let parsed = parse_input(TEST1_INPUT.lines());
+        let parsed = parse_input(TEST1_INPUT.lines()).unwrap();
         let mut memo = HashMap::new();
         let res = parsed
             .values()
@@ -149,7 +156,7 @@ dotted black bags contain no other bags.";
 
     #[test]
     fn test_contains() {
-        let parsed = parse_input(TEST1_INPUT.lines());
+        let parsed = parse_input(TEST1_INPUT.lines()).unwrap();
         let mut memo = HashMap::new();
         let res = parsed
             .get("shiny gold")
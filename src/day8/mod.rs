@@ -1,33 +1,47 @@
 use std::str::Lines;
 
-use crate::Day;
+use crate::error::Error;
+use crate::utils::{parse_lines, parse_number};
+use crate::{Day, Task};
 
 pub struct Day8 {}
 
-impl<'a> Day<'a> for Day8 {
-    fn get_tasks(&self) -> Vec<(usize, &dyn Fn() -> String)> {
+impl Day for Day8 {
+    type Input = Instructions;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Instructions::parse(input.lines())
+    }
+
+    fn get_tasks(&self) -> Vec<(usize, &Task<Self::Input>)> {
         vec![(1, &task1), (2, &task2)]
     }
 
-    fn get_day_number(&self) -> usize {
-        8
+    fn get_embedded_input(&self) -> Option<&'static str> {
+        Some(INPUT)
     }
 }
 
-fn task1() -> String {
-    let mut parsed = Instructions::parse(INPUT.lines());
+fn task1(instructions: &Instructions) -> Result<String, Error> {
+    let mut parsed = instructions.clone();
     parsed.iterate_until_terminated();
-    parsed.acc.to_string()
+    Ok(parsed.acc.to_string())
 }
 
-fn task2() -> String {
-    let mut parsed = Instructions::parse(INPUT.lines());
-    parsed.iterate_until_terminated_with_corruption();
-    parsed.acc.to_string()
+fn task2(instructions: &Instructions) -> Result<String, Error> {
+    let mut parsed = instructions.clone();
+    if parsed.iterate_until_terminated_with_corruption() {
+        Ok(parsed.acc.to_string())
+    } else {
+        Err(Error::no_solution(
+            "no single corrupted instruction lets the program terminate",
+        ))
+    }
 }
 
 const INPUT: &str = include_str!("input.txt");
 
+#[derive(Clone)]
 enum Instruction {
     NoOp(i32),
     Acc(i32),
@@ -35,35 +49,42 @@ enum Instruction {
 }
 use crate::day8::Instruction::*;
 impl Instruction {
-    fn parse(inst: &str) -> Instruction {
-        let op = &inst[0..3];
-        let sign = &inst[4..5];
-        let num = inst[5..]
-            .parse::<i32>()
-            .map(|v| if sign == "+" { v } else { v * -1 })
-            .unwrap();
+    fn parse(inst: &str) -> Result<Instruction, Error> {
+        let (op, arg) = inst
+            .split_once(' ')
+            .ok_or_else(|| Error::parse(format!("expected `op +/-num`, found `{}`", inst)))?;
+        let num = match arg.chars().next() {
+            Some('+') => parse_number::<i32>(&arg[1..]),
+            Some('-') => parse_number::<i32>(&arg[1..]).map(|v| -v),
+            _ => Err(Error::parse(format!(
+                "expected a signed argument, found `{}`",
+                arg
+            ))),
+        }
+        .map_err(|e| e.at_column(op.len() + 2))?;
         match op {
-            "nop" => NoOp(num),
-            "acc" => Acc(num),
-            "jmp" => Jump(num),
-            _ => panic!(""),
+            "nop" => Ok(NoOp(num)),
+            "acc" => Ok(Acc(num)),
+            "jmp" => Ok(Jump(num)),
+            _ => Err(Error::parse(format!("unknown opcode `{}`", op)).at_column(1)),
         }
     }
 }
 
-struct Instructions {
+#[derive(Clone)]
+pub struct Instructions {
     acc: i32,
     line: usize,
     instructions: Vec<(Instruction, bool)>,
 }
 
 impl Instructions {
-    fn parse(input: Lines) -> Instructions {
-        Instructions {
+    fn parse(input: Lines) -> Result<Instructions, Error> {
+        Ok(Instructions {
             acc: 0,
             line: 0,
-            instructions: input.map(|l| (Instruction::parse(l), false)).collect(),
-        }
+            instructions: parse_lines(input, |l| Instruction::parse(l).map(|i| (i, false)))?,
+        })
     }
 
     fn get_state(&self) -> NextState {
@@ -76,7 +97,7 @@ impl Instructions {
         }
     }
 
-    fn next(&mut self) -> () {
+    fn next(&mut self) {
         let this = self.instructions.get_mut(self.line).unwrap();
         this.1 = true;
         match this.0 {
@@ -107,28 +128,26 @@ impl Instructions {
         match this.0 {
             NoOp(num) => {
                 this.0 = Jump(num);
-                return true;
-            }
-            Acc(_) => {
-                return false;
+                true
             }
+            Acc(_) => false,
             Jump(num) => {
                 this.0 = NoOp(num);
-                return true;
+                true
             }
         }
     }
 
-    fn reset_state(&mut self) -> () {
+    fn reset_state(&mut self) {
         self.instructions.iter_mut().for_each(|e| e.1 = false);
         self.acc = 0;
         self.line = 0;
     }
 
-    fn iterate_until_terminated_with_corruption(&mut self) -> () {
+    fn iterate_until_terminated_with_corruption(&mut self) -> bool {
         let mut mutate_line: usize = 0;
         let mut clean_run: bool = false;
-        loop {
+        while mutate_line < self.instructions.len() {
             let changed = self.negate_instruction_at_line(mutate_line);
             if !changed && !clean_run {
                 clean_run = true;
@@ -140,10 +159,11 @@ impl Instructions {
             self.negate_instruction_at_line(mutate_line);
             mutate_line += 1;
             if res {
-                break;
+                return true;
             }
             self.reset_state();
         }
+        false
     }
 }
 
@@ -179,20 +199,27 @@ acc +6";
 
     #[test]
     fn test_parse() {
-        let parsed = Instructions::parse(TEST1_INPUT.lines());
+        let parsed = Instructions::parse(TEST1_INPUT.lines()).unwrap();
         assert_eq!(parsed.instructions.len(), 9);
     }
 
+    #[test]
+    fn test_parse_error() {
+        let err = Instructions::parse("nop +0\njpm +4".lines()).err().unwrap();
+        assert_eq!(err.to_string(), "line 2, column 1: unknown opcode `jpm`");
+        assert!(Instructions::parse("acc 4".lines()).is_err());
+    }
+
     #[test]
     fn test_task_1() {
-        let mut parsed = Instructions::parse(TEST1_INPUT.lines());
+        let mut parsed = Instructions::parse(TEST1_INPUT.lines()).unwrap();
         parsed.iterate_until_terminated();
         assert_eq!(parsed.acc, 5);
     }
 
     #[test]
     fn test_task_2_loop() {
-        let mut parsed = Instructions::parse(TEST1_INPUT.lines());
+        let mut parsed = Instructions::parse(TEST1_INPUT.lines()).unwrap();
         let res = parsed.iterate_until_terminated();
         assert_eq!(parsed.acc, 5);
         assert!(!res)
@@ -200,7 +227,7 @@ acc +6";
 
     #[test]
     fn test_task_2_terminate() {
-        let mut parsed = Instructions::parse(TEST2_INPUT.lines());
+        let mut parsed = Instructions::parse(TEST2_INPUT.lines()).unwrap();
         let res = parsed.iterate_until_terminated();
         assert_eq!(parsed.acc, 8);
         assert!(res)
@@ -1,44 +1,53 @@
 use std::str::Lines;
 
-use crate::Day;
+use crate::error::Error;
+use crate::utils::{parse_lines, parse_number};
+use crate::{Day, Task};
 
 pub struct Day9 {}
 
-impl<'a> Day<'a> for Day9 {
-    fn get_tasks(&self) -> Vec<(usize, &dyn Fn() -> String)> {
+impl Day for Day9 {
+    type Input = Stream;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error> {
+        Stream::parse(input.lines(), 25)
+    }
+
+    fn get_tasks(&self) -> Vec<(usize, &Task<Self::Input>)> {
         vec![(1, &task1), (2, &task2)]
     }
 
-    fn get_day_number(&self) -> usize {
-        9
+    fn get_embedded_input(&self) -> Option<&'static str> {
+        Some(INPUT)
     }
 }
 
-fn task1() -> String {
-    let parsed = Stream::parse(INPUT.lines(), 25);
-    parsed.find_invalid().unwrap().to_string()
+fn task1(stream: &Stream) -> Result<String, Error> {
+    stream
+        .find_invalid()
+        .map(|i| i.to_string())
+        .ok_or_else(|| Error::no_solution("every number is a sum of its preamble"))
 }
 
-fn task2() -> String {
-    let parsed = Stream::parse(INPUT.lines(), 25);
-    parsed.find_weakness().to_string()
+fn task2(stream: &Stream) -> Result<String, Error> {
+    stream
+        .find_weakness()
+        .map(|w| w.to_string())
+        .ok_or_else(|| Error::no_solution("no contiguous range sums to the invalid number"))
 }
 
-struct Stream {
+pub struct Stream {
     preamble_length: usize,
     stream_content: Vec<u64>,
 }
 
 impl Stream {
-    fn parse(lines: Lines, preamble_length: usize) -> Stream {
-        let stream_content = lines
-            .into_iter()
-            .map(|l| l.parse::<u64>().unwrap())
-            .collect();
-        Stream {
+    fn parse(lines: Lines, preamble_length: usize) -> Result<Stream, Error> {
+        let stream_content = parse_lines(lines, parse_number)?;
+        Ok(Stream {
             preamble_length,
             stream_content,
-        }
+        })
     }
 
     fn find_invalid(&self) -> Option<&u64> {
@@ -58,10 +67,10 @@ impl Stream {
                 }
             }
         }
-        return true;
+        true
     }
 
-    fn find_matching_contiguous_numbers(&self, target: &u64) -> Vec<&u64> {
+    fn find_matching_contiguous_numbers(&self, target: &u64) -> Option<Vec<&u64>> {
         let mut rolling_result: Vec<(u64, Vec<&u64>)> = vec![];
         for i in 0..self.stream_content.len() {
             let i_val = self.stream_content.get(i).unwrap();
@@ -71,20 +80,20 @@ impl Stream {
             });
             rolling_result.push((*i_val, vec![i_val]));
             match rolling_result.iter().find(|e| e.0 == *target) {
-                Some(e) => return e.1.clone(),
+                Some(e) => return Some(e.1.clone()),
                 None => false,
             };
             rolling_result.retain(|e| e.0 < *target)
         }
-        panic!("None found")
+        None
     }
 
-    fn find_weakness(&self) -> u64 {
-        let invalid_num = self.find_invalid().unwrap();
-        let matching_contiguous = self.find_matching_contiguous_numbers(invalid_num);
-        let min = matching_contiguous.iter().min().unwrap();
-        let max = matching_contiguous.iter().max().unwrap();
-        **min + **max
+    fn find_weakness(&self) -> Option<u64> {
+        let invalid_num = self.find_invalid()?;
+        let matching_contiguous = self.find_matching_contiguous_numbers(invalid_num)?;
+        let min = matching_contiguous.iter().min()?;
+        let max = matching_contiguous.iter().max()?;
+        Some(**min + **max)
     }
 }
 
@@ -117,13 +126,13 @@ mod tests {
 
     #[test]
     fn test_invalid() {
-        let parsed = Stream::parse(TEST1_INPUT.lines(), 5);
+        let parsed = Stream::parse(TEST1_INPUT.lines(), 5).unwrap();
         assert_eq!(*parsed.find_invalid().unwrap(), 127);
     }
 
     #[test]
     fn test_weakness() {
-        let parsed = Stream::parse(TEST1_INPUT.lines(), 5);
-        assert_eq!(parsed.find_weakness(), 62);
+        let parsed = Stream::parse(TEST1_INPUT.lines(), 5).unwrap();
+        assert_eq!(parsed.find_weakness(), Some(62));
     }
 }
@@ -0,0 +1,118 @@
+use std::fmt;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ErrorKind {
+    /// The puzzle input could not be read.
+    Input,
+    /// No puzzle input was given or found for the day.
+    MissingInput,
+    /// The puzzle input is malformed.
+    Parse,
+    /// The puzzle input is well formed but has no answer.
+    NoSolution,
+}
+
+/// An error raised while loading, parsing or solving a day's puzzle. The
+/// optional day, line and column are filled in as the error travels up to
+/// the runner, so it can report where things went wrong.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Error {
+    pub kind: ErrorKind,
+    pub message: String,
+    pub day: Option<usize>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+impl Error {
+    fn new(kind: ErrorKind, message: impl Into<String>) -> Error {
+        Error {
+            kind,
+            message: message.into(),
+            day: None,
+            line: None,
+            column: None,
+        }
+    }
+
+    pub fn input(message: impl Into<String>) -> Error {
+        Error::new(ErrorKind::Input, message)
+    }
+
+    pub fn missing_input(message: impl Into<String>) -> Error {
+        Error::new(ErrorKind::MissingInput, message)
+    }
+
+    pub fn parse(message: impl Into<String>) -> Error {
+        Error::new(ErrorKind::Parse, message)
+    }
+
+    pub fn no_solution(message: impl Into<String>) -> Error {
+        Error::new(ErrorKind::NoSolution, message)
+    }
+
+    pub fn in_day(self, day: usize) -> Error {
+        Error {
+            day: Some(day),
+            ..self
+        }
+    }
+
+    pub fn at_line(self, line: usize) -> Error {
+        Error {
+            line: Some(line),
+            ..self
+        }
+    }
+
+    pub fn at_column(self, column: usize) -> Error {
+        Error {
+            column: Some(column),
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let location: Vec<String> = [
+            self.day.map(|d| format!("day {}", d)),
+            self.line.map(|l| format!("line {}", l)),
+            self.column.map(|c| format!("column {}", c)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if location.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", location.join(", "), self.message)
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            Error::parse("unknown opcode `jpm`")
+                .at_line(14)
+                .in_day(8)
+                .to_string(),
+            "day 8, line 14: unknown opcode `jpm`"
+        );
+        assert_eq!(
+            Error::parse("expected `F` or `B`")
+                .at_line(3)
+                .at_column(2)
+                .to_string(),
+            "line 3, column 2: expected `F` or `B`"
+        );
+        assert_eq!(Error::no_solution("none").to_string(), "none");
+    }
+}
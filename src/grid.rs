@@ -0,0 +1,238 @@
+use crate::error::Error;
+
+/// The eight compass directions as `(dx, dy)` steps, with `y` growing down.
+pub const DIRECTIONS: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A rectangular grid of cells stored row by row, as puzzle inputs draw them.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Grid<T> {
+    width: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Parses one row per line, turning each character into a cell with
+    /// `cell`. Unknown characters and ragged rows are reported with their
+    /// position.
+    pub fn parse(input: &str, cell: impl Fn(char) -> Option<T>) -> Result<Grid<T>, Error> {
+        let mut width = None;
+        let mut cells = vec![];
+        for (y, line) in input.lines().enumerate() {
+            let mut row_width = 0;
+            for (x, c) in line.chars().enumerate() {
+                let parsed = cell(c).ok_or_else(|| {
+                    Error::parse(format!("unexpected character `{}`", c))
+                        .at_line(y + 1)
+                        .at_column(x + 1)
+                })?;
+                cells.push(parsed);
+                row_width += 1;
+            }
+            match width {
+                None => width = Some(row_width),
+                Some(w) if w != row_width => {
+                    return Err(Error::parse(format!(
+                        "expected a row of width {}, found {}",
+                        w, row_width
+                    ))
+                    .at_line(y + 1))
+                }
+                Some(_) => {}
+            }
+        }
+        match width {
+            Some(width) if width > 0 => Ok(Grid { width, cells }),
+            _ => Err(Error::parse("the grid is empty")),
+        }
+    }
+
+    /// Builds a grid from cells laid out row by row.
+    pub fn from_cells(width: usize, cells: Vec<T>) -> Grid<T> {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width),
+            "cells must fill whole rows"
+        );
+        Grid { width, cells }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.len() / self.width
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: T) {
+        let width = self.width;
+        assert!(x < width, "x is outside the grid");
+        self.cells[y * width + x] = value;
+    }
+
+    /// The cells row by row, matching the order of [`Grid::positions`].
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i % width, i / width))
+    }
+
+    /// Moves one step from `(x, y)` in `direction`, if that stays on the grid.
+    pub fn step(&self, (x, y): (usize, usize), (dx, dy): (isize, isize)) -> Option<(usize, usize)> {
+        let x = x.checked_add_signed(dx)?;
+        let y = y.checked_add_signed(dy)?;
+        if x < self.width && y < self.height() {
+            Some((x, y))
+        } else {
+            None
+        }
+    }
+
+    /// The positions of the (up to eight) cells surrounding `(x, y)`.
+    pub fn neighbours(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        DIRECTIONS.iter().filter_map(move |d| self.step((x, y), *d))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+}
+
+/// Transforms that rearrange the cells of a grid into a new one.
+impl<T: Clone> Grid<T> {
+    /// Turns the grid a quarter turn clockwise.
+    pub fn rotate(&self) -> Grid<T> {
+        let height = self.height();
+        let cells = (0..self.width)
+            .flat_map(|y| (0..height).map(move |x| (x, y)))
+            .map(|(x, y)| self.cells[(height - 1 - x) * self.width + y].clone())
+            .collect();
+        Grid {
+            width: height,
+            cells,
+        }
+    }
+
+    /// Mirrors the grid left to right.
+    pub fn flip(&self) -> Grid<T> {
+        let cells = self
+            .cells
+            .chunks(self.width)
+            .flat_map(|row| row.iter().rev().cloned())
+            .collect();
+        Grid {
+            width: self.width,
+            cells,
+        }
+    }
+
+    /// All eight ways of placing the grid: its four rotations, then the four
+    /// rotations of its mirror image.
+    pub fn orientations(&self) -> Vec<Grid<T>> {
+        let mut orientations = Vec::with_capacity(8);
+        for start in [self.clone(), self.flip()] {
+            let mut grid = start;
+            for _ in 0..4 {
+                let next = grid.rotate();
+                orientations.push(grid);
+                grid = next;
+            }
+        }
+        orientations
+    }
+
+    /// Copies out the `width` by `height` block whose top left is `(x, y)`.
+    pub fn crop(&self, x: usize, y: usize, width: usize, height: usize) -> Grid<T> {
+        assert!(
+            width > 0 && x + width <= self.width && y + height <= self.height(),
+            "the block must lie inside the grid"
+        );
+        let cells = (y..y + height)
+            .flat_map(|y| self.row(y)[x..x + width].iter().cloned())
+            .collect();
+        Grid { width, cells }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Grid;
+
+    fn parse(input: &str) -> Grid<bool> {
+        Grid::parse(input, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = parse("#..\n.#.");
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.get(1, 1), Some(&true));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 2), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let ragged = Grid::parse("##\n#", |c| Some(c == '#')).err().unwrap();
+        assert_eq!(
+            ragged.to_string(),
+            "line 2: expected a row of width 2, found 1"
+        );
+        assert!(Grid::parse("", Some).is_err());
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = parse("...\n...\n...");
+        assert_eq!(grid.neighbours(1, 1).count(), 8);
+        assert_eq!(
+            grid.neighbours(0, 0).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1), (1, 1)]
+        );
+        assert_eq!(grid.step((2, 2), (1, 0)), None);
+    }
+
+    #[test]
+    fn test_transforms() {
+        let grid = parse("#..\n.##");
+        assert_eq!(grid.rotate(), parse(".#\n#.\n#."));
+        assert_eq!(grid.flip(), parse("..#\n##."));
+        assert_eq!(grid.rotate().rotate().rotate().rotate(), grid);
+        assert_eq!(grid.crop(1, 0, 2, 2), parse("..\n##"));
+        assert_eq!(grid.row(1), &[false, true, true]);
+        assert_eq!(grid.column(2).collect::<Vec<_>>(), vec![&false, &true]);
+        let orientations = grid.orientations();
+        assert_eq!(orientations.len(), 8);
+        assert!(orientations.contains(&grid.flip().rotate()));
+        assert!(orientations.contains(&grid.rotate().flip()));
+    }
+}
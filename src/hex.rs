@@ -0,0 +1,87 @@
+/// The six directions out of a hexagon whose rows run east to west.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum HexDirection {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+impl HexDirection {
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::East,
+        HexDirection::SouthEast,
+        HexDirection::SouthWest,
+        HexDirection::West,
+        HexDirection::NorthWest,
+        HexDirection::NorthEast,
+    ];
+
+    /// The step in axial coordinates, as `(q, r)`.
+    fn offset(self) -> (i32, i32) {
+        match self {
+            HexDirection::East => (1, 0),
+            HexDirection::SouthEast => (0, 1),
+            HexDirection::SouthWest => (-1, 1),
+            HexDirection::West => (-1, 0),
+            HexDirection::NorthWest => (0, -1),
+            HexDirection::NorthEast => (1, -1),
+        }
+    }
+}
+
+/// A hexagon in axial coordinates: `q` counts steps east and `r` steps south
+/// east, so every hexagon has exactly one position.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub struct Hex {
+    pub q: i32,
+    pub r: i32,
+}
+
+impl Hex {
+    pub fn new(q: i32, r: i32) -> Hex {
+        Hex { q, r }
+    }
+
+    pub fn step(self, direction: HexDirection) -> Hex {
+        let (dq, dr) = direction.offset();
+        Hex::new(self.q + dq, self.r + dr)
+    }
+
+    pub fn neighbours(self) -> impl Iterator<Item = Hex> {
+        HexDirection::ALL.iter().map(move |d| self.step(*d))
+    }
+
+    /// The number of steps between two hexagons.
+    pub fn distance(self, other: Hex) -> u32 {
+        let (dq, dr) = (self.q - other.q, self.r - other.r);
+        (dq.unsigned_abs() + dr.unsigned_abs() + (dq + dr).unsigned_abs()) / 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Hex, HexDirection};
+
+    #[test]
+    fn test_steps() {
+        use HexDirection::*;
+        let origin = Hex::default();
+        let back = [NorthEast, NorthWest, SouthWest, SouthEast, East, West]
+            .iter()
+            .fold(origin, |h, d| h.step(*d));
+        assert_eq!(back, origin);
+        assert_eq!(origin.step(East).step(SouthWest), origin.step(SouthEast));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let origin = Hex::default();
+        let neighbours: Vec<Hex> = origin.neighbours().collect();
+        assert_eq!(neighbours.len(), 6);
+        assert!(neighbours.iter().all(|n| n.distance(origin) == 1));
+        assert_eq!(Hex::new(2, -3).distance(Hex::new(-1, 1)), 4);
+    }
+}
@@ -0,0 +1,62 @@
+use std::borrow::Cow;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use crate::cli::{InputSource, Options};
+use crate::error::Error;
+use crate::AnyDay;
+
+/// Resolves the puzzle input for a day: an explicit `--input` wins, then
+/// `dayNN.txt` in the inputs directory, then the copy embedded at build time.
+pub fn load_input(day: &dyn AnyDay, options: &Options) -> Result<Cow<'static, str>, Error> {
+    match &options.input {
+        Some(InputSource::Stdin) => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| Error::input(format!("failed to read stdin: {}", e)))?;
+            return Ok(Cow::Owned(input));
+        }
+        Some(InputSource::File(path)) => return read_file(path).map(Cow::Owned),
+        None => {}
+    }
+    let path = input_path(&options.inputs_dir, day.get_day_number());
+    if path.is_file() {
+        return read_file(&path).map(Cow::Owned);
+    }
+    day.get_embedded_input().map(Cow::Borrowed).ok_or_else(|| {
+        Error::missing_input(format!(
+            "no input found, pass `--input` or create {}",
+            path.display()
+        ))
+    })
+}
+
+pub fn input_path(inputs_dir: &Path, day: usize) -> PathBuf {
+    inputs_dir.join(format!("day{:02}.txt", day))
+}
+
+fn read_file(path: &Path) -> Result<String, Error> {
+    fs::read_to_string(path)
+        .map_err(|e| Error::input(format!("failed to read {}: {}", path.display(), e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::input_path;
+
+    #[test]
+    fn test_input_path() {
+        assert_eq!(
+            input_path(Path::new("inputs"), 7),
+            Path::new("inputs/day07.txt")
+        );
+        assert_eq!(
+            input_path(Path::new("inputs"), 12),
+            Path::new("inputs/day12.txt")
+        );
+    }
+}
@@ -1,57 +1,344 @@
-use day1::Day1;
-use day2::Day2;
-use day3::Day3;
-use day4::Day4;
-use day5::Day5;
-use day6::Day6;
-use day7::Day7;
-use day8::Day8;
-use day9::Day9;
-use std::time::Instant;
-
-pub mod day1;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7;
-pub mod day8;
-pub mod day9;
+use answers::Answers;
+use cli::Options;
+use error::Error;
+use report::{DayReport, InputReport, Status, TaskReport, Timing};
+use std::any::Any;
+use std::ops::RangeInclusive;
+use std::time::{Duration, Instant};
+
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod error;
+pub mod grid;
+pub mod hex;
+pub mod input;
+pub mod parallel;
+pub mod report;
 pub mod utils;
 
-pub trait Day<'a> {
-    fn get_tasks(&self) -> Vec<(usize, &dyn Fn() -> String)>;
+pub type Task<I> = dyn Fn(&I) -> Result<String, Error> + Send + Sync;
+
+/// A day's puzzle. The input is parsed once into `Input`, which every task
+/// then solves, so parsing and solving can be timed (and tested) apart.
+pub trait Day: DayNumber + Send + Sync {
+    type Input: Send + Sync + 'static;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, Error>;
+    fn get_tasks(&self) -> Vec<(usize, &Task<Self::Input>)>;
+    fn get_embedded_input(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+/// A parsed input whose type has been erased so days can share a registry.
+pub type Parsed = Box<dyn Any + Send + Sync>;
+
+/// The object-safe view of a [`Day`] the runner works with, implemented for
+/// every `Day` by downcasting the erased input back to `Day::Input`.
+pub trait AnyDay: DayNumber + Send + Sync {
+    fn parse(&self, input: &str) -> Result<Parsed, Error>;
+    fn get_task_numbers(&self) -> Vec<usize>;
+    fn solve(&self, task: usize, parsed: &Parsed) -> Result<String, Error>;
+    fn get_embedded_input(&self) -> Option<&'static str>;
+}
+
+impl<D: Day> AnyDay for D {
+    fn parse(&self, input: &str) -> Result<Parsed, Error> {
+        Day::parse(self, input).map(|p| Box::new(p) as Parsed)
+    }
+
+    fn get_task_numbers(&self) -> Vec<usize> {
+        self.get_tasks().iter().map(|t| t.0).collect()
+    }
+
+    fn solve(&self, task: usize, parsed: &Parsed) -> Result<String, Error> {
+        let input = parsed
+            .downcast_ref::<D::Input>()
+            .expect("parsed input comes from the same day");
+        let tasks = self.get_tasks();
+        let (_, solve) = tasks
+            .iter()
+            .find(|t| t.0 == task)
+            .expect("only registered tasks are solved");
+        solve(input)
+    }
+
+    fn get_embedded_input(&self) -> Option<&'static str> {
+        Day::get_embedded_input(self)
+    }
+}
+
+/// Implemented for each day by `days!` from its registered number, so the
+/// number cannot drift from the registry.
+pub trait DayNumber {
     fn get_day_number(&self) -> usize;
 }
 
-fn get_days<'a>() -> Vec<&'a dyn Day<'a>> {
-    vec![
-        &Day1 {},
-        &Day2 {},
-        &Day3 {},
-        &Day4 {},
-        &Day5 {},
-        &Day6 {},
-        &Day7 {},
-        &Day8 {},
-        &Day9 {},
-    ]
+/// Registers the day modules: declares each `pub mod`, implements
+/// [`DayNumber`] for its `Day` struct and adds it to [`get_days`], which the
+/// runner and the CLI discover days from. Adding a day is one line here.
+macro_rules! days {
+    ($($number:literal => $module:ident::$day:ident),* $(,)?) => {
+        $(
+            pub mod $module;
+
+            impl DayNumber for $module::$day {
+                fn get_day_number(&self) -> usize {
+                    $number
+                }
+            }
+        )*
+
+        fn get_days() -> Vec<&'static dyn AnyDay> {
+            vec![$(&$module::$day {}),*]
+        }
+    };
+}
+
+days! {
+    1 => day1::Day1,
+    2 => day2::Day2,
+    3 => day3::Day3,
+    4 => day4::Day4,
+    5 => day5::Day5,
+    6 => day6::Day6,
+    7 => day7::Day7,
+    8 => day8::Day8,
+    9 => day9::Day9,
+    10 => day10::Day10,
+    11 => day11::Day11,
+    12 => day12::Day12,
+    13 => day13::Day13,
+    14 => day14::Day14,
+    15 => day15::Day15,
+    16 => day16::Day16,
+    17 => day17::Day17,
+    18 => day18::Day18,
+    19 => day19::Day19,
+    20 => day20::Day20,
+    21 => day21::Day21,
+    22 => day22::Day22,
+    23 => day23::Day23,
+    24 => day24::Day24,
+    25 => day25::Day25,
 }
 
-pub fn run() {
-    get_days().iter().for_each(|d| benchmark(*d))
+pub fn run(options: &Options) -> Result<(), String> {
+    let days: Vec<&dyn AnyDay> = get_days()
+        .into_iter()
+        .filter(|d| options.days.includes(d.get_day_number()))
+        .collect();
+    if let cli::DaySelection::Days(ranges) = &options.days {
+        let registered: Vec<usize> = days.iter().map(|d| d.get_day_number()).collect();
+        if let Some(missing) = ranges
+            .iter()
+            .find_map(|r| first_unregistered(r, &registered))
+        {
+            return Err(format!("day {} does not exist", missing));
+        }
+    }
+    // A task number only some days have is run where it exists, unless a day
+    // named on its own lacks it or no selected day has it at all.
+    let days: Vec<&dyn AnyDay> = match options.task {
+        Some(task) => {
+            if let Some(day) = days.iter().find(|d| {
+                options.days.names(d.get_day_number()) && !d.get_task_numbers().contains(&task)
+            }) {
+                return Err(format!(
+                    "task {} does not exist for day {}",
+                    task,
+                    day.get_day_number()
+                ));
+            }
+            let with_task: Vec<&dyn AnyDay> = days
+                .into_iter()
+                .filter(|d| d.get_task_numbers().contains(&task))
+                .collect();
+            if with_task.is_empty() {
+                return Err(format!("task {} does not exist for any selected day", task));
+            }
+            with_task
+        }
+        None => days,
+    };
+    let answers = match &options.answers {
+        Some(path) => Answers::load(path).map_err(|e| e.to_string())?,
+        None => Answers::default(),
+    };
+    let started = Instant::now();
+    let (reports, summed) = run_days(&days, options, &answers);
+    let timing = (options.jobs > 1).then(|| Timing {
+        wall: started.elapsed(),
+        summed,
+    });
+    print!("{}", report::render(options.format, &reports, timing));
+    let statuses: Vec<Status> = reports
+        .iter()
+        .flat_map(|d| d.tasks.iter().map(|t| t.status()))
+        .collect();
+    let failed = statuses.iter().filter(|s| **s == Status::Error).count();
+    let mismatched = statuses.iter().filter(|s| **s == Status::Fail).count();
+    match (failed, mismatched) {
+        (0, 0) => Ok(()),
+        (failed, 0) => Err(format!("{} task(s) failed", failed)),
+        (0, mismatched) => Err(format!(
+            "{} answer(s) did not match the expected answer",
+            mismatched
+        )),
+        (failed, mismatched) => Err(format!(
+            "{} task(s) failed and {} answer(s) did not match the expected answer",
+            failed, mismatched
+        )),
+    }
 }
 
-fn benchmark(day: &dyn Day) {
-    println!("Day: {}", day.get_day_number());
-    day.get_tasks().iter().for_each(|t| {
+/// The first day in `range` that is not registered, found by walking the
+/// registered days in it rather than the range itself, which may be huge.
+fn first_unregistered(range: &RangeInclusive<usize>, registered: &[usize]) -> Option<usize> {
+    let mut next = *range.start();
+    for day in registered.iter().filter(|d| range.contains(d)) {
+        if *day != next {
+            return Some(next);
+        }
+        next += 1;
+    }
+    (next <= *range.end()).then_some(next)
+}
+
+/// Loads and parses each day's input and then solves every selected task,
+/// spreading both phases over `options.jobs` threads. Also returns the time
+/// summed across all of that work, which exceeds the wall time when jobs
+/// overlap.
+fn run_days(
+    days: &[&dyn AnyDay],
+    options: &Options,
+    answers: &Answers,
+) -> (Vec<DayReport>, Duration) {
+    let inputs = parallel::map(days, options.jobs, |d| {
+        let now = Instant::now();
+        prepare_input(*d, options)
+            .map_err(|e| e.in_day(d.get_day_number()))
+            .map(|i| (i, now.elapsed()))
+    });
+    let tasks: Vec<(usize, usize)> = days
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| inputs[*i].is_ok())
+        .flat_map(|(i, d)| selected_tasks(*d, options).into_iter().map(move |t| (i, t)))
+        .collect();
+    let task_reports = parallel::map(&tasks, options.jobs, |(i, task)| {
         let now = Instant::now();
-        let res = t.1();
-        let elapsed = now.elapsed();
-        println!(
-            "Finished task: {}, result: {}, elapsed: {:.2?}",
-            t.0, res, elapsed
-        )
+        let parsed = &inputs[*i]
+            .as_ref()
+            .expect("only days with input are run")
+            .0
+             .0;
+        let report = run_task(days[*i], *task, parsed, options, answers);
+        (report, now.elapsed())
     });
+    let mut summed: Duration = task_reports.iter().map(|r| r.1).sum();
+    let mut task_reports = task_reports.into_iter();
+    let reports = days
+        .iter()
+        .zip(inputs)
+        .map(|(d, input)| {
+            let number = d.get_day_number();
+            let selected = selected_tasks(*d, options);
+            match input {
+                Ok(((_, report), elapsed)) => {
+                    summed += elapsed;
+                    DayReport {
+                        day: number,
+                        input: Ok(report),
+                        tasks: task_reports
+                            .by_ref()
+                            .take(selected.len())
+                            .map(|r| r.0)
+                            .collect(),
+                    }
+                }
+                Err(e) => DayReport {
+                    day: number,
+                    tasks: selected
+                        .iter()
+                        .map(|t| TaskReport {
+                            task: *t,
+                            result: Err(e.clone()),
+                            expected: answers.get(number, *t).map(String::from),
+                            elapsed: None,
+                            stats: None,
+                        })
+                        .collect(),
+                    input: Err(e),
+                },
+            }
+        })
+        .collect();
+    (reports, summed)
+}
+
+/// Reads and parses a day's input, timing the two steps separately. In bench
+/// mode the parse is repeated to gather statistics like a task's solve.
+fn prepare_input(day: &dyn AnyDay, options: &Options) -> Result<(Parsed, InputReport), Error> {
+    let now = Instant::now();
+    let input = input::load_input(day, options)?;
+    let load = now.elapsed();
+    match &options.bench {
+        Some(bench) => {
+            let (parsed, stats) = bench::measure(|| day.parse(&input), bench)?;
+            let report = InputReport {
+                load,
+                parse: stats.median,
+                parse_stats: Some(stats),
+            };
+            Ok((parsed, report))
+        }
+        None => {
+            let now = Instant::now();
+            let parsed = day.parse(&input)?;
+            let report = InputReport {
+                load,
+                parse: now.elapsed(),
+                parse_stats: None,
+            };
+            Ok((parsed, report))
+        }
+    }
+}
+
+fn selected_tasks(day: &dyn AnyDay, options: &Options) -> Vec<usize> {
+    day.get_task_numbers()
+        .into_iter()
+        .filter(|t| options.task.is_none_or(|n| n == *t))
+        .collect()
+}
+
+fn run_task(
+    day: &dyn AnyDay,
+    task: usize,
+    parsed: &Parsed,
+    options: &Options,
+    answers: &Answers,
+) -> TaskReport {
+    let (result, elapsed, stats) = match &options.bench {
+        Some(bench) => match bench::measure(|| day.solve(task, parsed), bench) {
+            Ok((res, stats)) => (Ok(res), Some(stats.median), Some(stats)),
+            Err(e) => (Err(e), None, None),
+        },
+        None => {
+            let now = Instant::now();
+            let result = day.solve(task, parsed);
+            let elapsed = result.is_ok().then(|| now.elapsed());
+            (result, elapsed, None)
+        }
+    };
+    let number = day.get_day_number();
+    TaskReport {
+        task,
+        result: result.map_err(|e| e.in_day(number)),
+        expected: answers.get(number, task).map(String::from),
+        elapsed,
+        stats,
+    }
 }
@@ -1,6 +1,19 @@
-use aoc_2020_rust;
+use aoc_2020_rust::cli::{parse_args, Command, USAGE};
 use aoc_2020_rust::run;
+use std::process::exit;
 
 fn main() {
-    run();
+    match parse_args(std::env::args().skip(1)) {
+        Ok(Command::Help) => println!("{}", USAGE),
+        Ok(Command::Run(options)) => {
+            if let Err(e) = run(&options) {
+                eprintln!("error: {}", e);
+                exit(1);
+            }
+        }
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            exit(2);
+        }
+    }
 }
@@ -0,0 +1,65 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// Maps `f` over `items` on up to `jobs` scoped threads, handing out items
+/// one at a time so a slow item does not hold up a whole batch. Results come
+/// back in the order of `items` however the work was scheduled.
+pub fn map<T: Sync, R: Send>(items: &[T], jobs: usize, f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    if jobs <= 1 || items.len() <= 1 {
+        return items.iter().map(f).collect();
+    }
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<R>>> = Mutex::new(items.iter().map(|_| None).collect());
+    thread::scope(|s| {
+        for _ in 0..jobs.min(items.len()) {
+            s.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                match items.get(i) {
+                    Some(item) => {
+                        let res = f(item);
+                        results.lock().expect("a worker panicked")[i] = Some(res);
+                    }
+                    None => break,
+                }
+            });
+        }
+    });
+    results
+        .into_inner()
+        .expect("a worker panicked")
+        .into_iter()
+        .map(|r| r.expect("every item is mapped once the scope ends"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+    use std::thread;
+    use std::time::Duration;
+
+    use super::map;
+
+    #[test]
+    fn test_keeps_order() {
+        let items: Vec<u64> = (0..20).collect();
+        let res = map(&items, 4, |i| {
+            thread::sleep(Duration::from_millis(20 - i));
+            i * 2
+        });
+        assert_eq!(res, (0..20).map(|i| i * 2).collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn test_uses_threads() {
+        let threads = Mutex::new(HashSet::new());
+        map(&[0; 8], 4, |_| {
+            thread::sleep(Duration::from_millis(10));
+            threads.lock().unwrap().insert(thread::current().id());
+        });
+        assert!(threads.into_inner().unwrap().len() > 1);
+        assert_eq!(map(&[1, 2, 3], 1, |i| i + 1), vec![2, 3, 4]);
+    }
+}
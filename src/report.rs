@@ -0,0 +1,504 @@
+use std::fmt::Write;
+use std::time::Duration;
+
+use crate::bench::Stats;
+use crate::error::{Error, ErrorKind};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl Format {
+    pub fn parse(name: &str) -> Option<Format> {
+        match name {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            "markdown" | "md" => Some(Format::Markdown),
+            _ => None,
+        }
+    }
+}
+
+pub struct DayReport {
+    pub day: usize,
+    /// How long reading and parsing the input took, or why either failed.
+    pub input: Result<InputReport, Error>,
+    pub tasks: Vec<TaskReport>,
+}
+
+pub struct InputReport {
+    pub load: Duration,
+    pub parse: Duration,
+    pub parse_stats: Option<Stats>,
+}
+
+pub struct TaskReport {
+    pub task: usize,
+    pub result: Result<String, Error>,
+    /// The recorded answer for this task, if there is one.
+    pub expected: Option<String>,
+    pub elapsed: Option<Duration>,
+    pub stats: Option<Stats>,
+}
+
+/// How long a run with several jobs took, against the time its work would
+/// have taken one after another.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Timing {
+    pub wall: Duration,
+    pub summed: Duration,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Status {
+    Pass,
+    Fail,
+    Unknown,
+    /// The day had no input, so the task was not run.
+    Skipped,
+    Error,
+}
+
+impl Status {
+    fn name(&self) -> &'static str {
+        match self {
+            Status::Pass => "pass",
+            Status::Fail => "fail",
+            Status::Unknown => "unknown",
+            Status::Skipped => "skipped",
+            Status::Error => "error",
+        }
+    }
+}
+
+impl TaskReport {
+    pub fn status(&self) -> Status {
+        match (&self.result, &self.expected) {
+            (Err(e), _) if e.kind == ErrorKind::MissingInput => Status::Skipped,
+            (Err(_), _) => Status::Error,
+            (Ok(_), None) => Status::Unknown,
+            (Ok(res), Some(expected)) if res == expected => Status::Pass,
+            (Ok(_), Some(_)) => Status::Fail,
+        }
+    }
+
+    /// The suffix the text format appends to a finished task's line.
+    fn check(&self) -> String {
+        match self.status() {
+            Status::Pass => ", check: pass".to_string(),
+            Status::Fail => format!(
+                ", check: fail (expected {})",
+                self.expected.as_deref().unwrap_or_default()
+            ),
+            Status::Unknown | Status::Skipped | Status::Error => String::new(),
+        }
+    }
+}
+
+/// Renders the reports, followed by a total for the run when `timing` is
+/// given: a closing line in text, and a row with the status `total` in the
+/// other formats.
+pub fn render(format: Format, days: &[DayReport], timing: Option<Timing>) -> String {
+    let mut out = String::new();
+    match format {
+        Format::Text => render_text(&mut out, days, timing),
+        Format::Json => render_json(&mut out, days, timing),
+        Format::Csv => render_csv(&mut out, days, timing),
+        Format::Markdown => render_markdown(&mut out, days, timing),
+    }
+    .expect("writing to a String cannot fail");
+    out
+}
+
+fn rows(days: &[DayReport]) -> impl Iterator<Item = (&DayReport, &TaskReport)> {
+    days.iter()
+        .flat_map(|d| d.tasks.iter().map(move |t| (d, t)))
+}
+
+fn has_stats(days: &[DayReport]) -> bool {
+    rows(days).any(|(_, t)| t.stats.is_some())
+}
+
+fn parse_nanos(day: &DayReport) -> Option<u128> {
+    day.input.as_ref().ok().map(|i| i.parse.as_nanos())
+}
+
+fn stats_fields(stats: &Stats) -> [(&'static str, u128); 5] {
+    [
+        ("min_ns", stats.min.as_nanos()),
+        ("median_ns", stats.median.as_nanos()),
+        ("mean_ns", stats.mean.as_nanos()),
+        ("p95_ns", stats.p95.as_nanos()),
+        ("stddev_ns", stats.stddev.as_nanos()),
+    ]
+}
+
+fn render_text(out: &mut String, days: &[DayReport], timing: Option<Timing>) -> std::fmt::Result {
+    for day in days {
+        writeln!(out, "Day: {}", day.day)?;
+        match &day.input {
+            Err(e) if e.kind == ErrorKind::MissingInput => {
+                writeln!(out, "Skipped, {}", e)?;
+                continue;
+            }
+            Err(e) if e.kind == ErrorKind::Input => {
+                writeln!(out, "Failed to load input, error: {}", e)?;
+                continue;
+            }
+            Err(e) => {
+                writeln!(out, "Failed to parse input, error: {}", e)?;
+                continue;
+            }
+            Ok(input) => match &input.parse_stats {
+                Some(stats) => {
+                    writeln!(out, "Loaded input, elapsed: {:.2?}", input.load)?;
+                    writeln!(out, "Parsed input, {}", stats)?
+                }
+                None => writeln!(out, "Parsed input, elapsed: {:.2?}", input.parse)?,
+            },
+        }
+        for t in &day.tasks {
+            match (&t.result, &t.stats) {
+                (Ok(res), Some(stats)) => writeln!(
+                    out,
+                    "Finished task: {}, result: {}, {}{}",
+                    t.task,
+                    res,
+                    stats,
+                    t.check()
+                )?,
+                (Ok(res), None) => writeln!(
+                    out,
+                    "Finished task: {}, result: {}, elapsed: {:.2?}{}",
+                    t.task,
+                    res,
+                    t.elapsed.unwrap_or_default(),
+                    t.check()
+                )?,
+                (Err(e), _) => writeln!(out, "Failed task: {}, error: {}", t.task, e)?,
+            }
+        }
+    }
+    if let Some(timing) = timing {
+        writeln!(
+            out,
+            "Total wall time: {:.2?}, summed time: {:.2?}",
+            timing.wall, timing.summed
+        )?;
+    }
+    Ok(())
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn render_json(out: &mut String, days: &[DayReport], timing: Option<Timing>) -> std::fmt::Result {
+    let mut objects: Vec<String> = rows(days)
+        .map(|(day, t)| {
+            let mut fields = vec![
+                format!("\"day\":{}", day.day),
+                format!("\"task\":{}", t.task),
+                format!("\"status\":{}", json_string(t.status().name())),
+            ];
+            if let Some(expected) = &t.expected {
+                fields.push(format!("\"expected\":{}", json_string(expected)));
+            }
+            match &t.result {
+                Ok(res) => fields.push(format!("\"answer\":{}", json_string(res))),
+                Err(e) => fields.push(format!("\"error\":{}", json_string(&e.to_string()))),
+            }
+            if let Some(elapsed) = t.elapsed {
+                fields.push(format!("\"elapsed_ns\":{}", elapsed.as_nanos()));
+            }
+            if let Some(parse) = parse_nanos(day) {
+                fields.push(format!("\"parse_ns\":{}", parse));
+            }
+            if let Some(stats) = &t.stats {
+                fields.push(format!("\"runs\":{}", stats.runs));
+                fields.extend(
+                    stats_fields(stats)
+                        .iter()
+                        .map(|(name, v)| format!("\"{}\":{}", name, v)),
+                );
+            }
+            format!("{{{}}}", fields.join(","))
+        })
+        .collect();
+    if let Some(timing) = timing {
+        objects.push(format!(
+            "{{\"status\":\"total\",\"elapsed_ns\":{},\"summed_ns\":{}}}",
+            timing.wall.as_nanos(),
+            timing.summed.as_nanos()
+        ));
+    }
+    writeln!(out, "[{}]", objects.join(","))
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn render_csv(out: &mut String, days: &[DayReport], timing: Option<Timing>) -> std::fmt::Result {
+    let with_stats = has_stats(days);
+    write!(
+        out,
+        "day,task,status,answer,expected,elapsed_ns,parse_ns,error"
+    )?;
+    if with_stats {
+        write!(out, ",runs,min_ns,median_ns,mean_ns,p95_ns,stddev_ns")?;
+    }
+    if timing.is_some() {
+        write!(out, ",summed_ns")?;
+    }
+    writeln!(out)?;
+    for (day, t) in rows(days) {
+        let (answer, error) = match &t.result {
+            Ok(res) => (csv_field(res), String::new()),
+            Err(e) => (String::new(), csv_field(&e.to_string())),
+        };
+        let elapsed = t.elapsed.map(|e| e.as_nanos().to_string());
+        write!(
+            out,
+            "{},{},{},{},{},{},{},{}",
+            day.day,
+            t.task,
+            t.status().name(),
+            answer,
+            csv_field(t.expected.as_deref().unwrap_or_default()),
+            elapsed.unwrap_or_default(),
+            parse_nanos(day).map(|p| p.to_string()).unwrap_or_default(),
+            error
+        )?;
+        if with_stats {
+            match &t.stats {
+                Some(stats) => {
+                    write!(out, ",{}", stats.runs)?;
+                    for (_, v) in stats_fields(stats) {
+                        write!(out, ",{}", v)?;
+                    }
+                }
+                None => write!(out, ",,,,,,")?,
+            }
+        }
+        if timing.is_some() {
+            write!(out, ",")?;
+        }
+        writeln!(out)?;
+    }
+    if let Some(timing) = timing {
+        write!(out, ",,total,,,{},,", timing.wall.as_nanos())?;
+        if with_stats {
+            write!(out, ",,,,,,")?;
+        }
+        writeln!(out, ",{}", timing.summed.as_nanos())?;
+    }
+    Ok(())
+}
+
+fn markdown_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+fn render_markdown(
+    out: &mut String,
+    days: &[DayReport],
+    timing: Option<Timing>,
+) -> std::fmt::Result {
+    writeln!(out, "| Day | Task | Answer | Expected | Elapsed | Status |")?;
+    writeln!(out, "| --: | ---: | :----- | :------- | ------: | :----- |")?;
+    for (day, t) in rows(days) {
+        let answer = match &t.result {
+            Ok(res) => markdown_cell(res),
+            Err(e) => markdown_cell(&e.to_string()),
+        };
+        let elapsed = t.elapsed.map(|e| format!("{:.2?}", e)).unwrap_or_default();
+        writeln!(
+            out,
+            "| {} | {} | {} | {} | {} | {} |",
+            day.day,
+            t.task,
+            answer,
+            markdown_cell(t.expected.as_deref().unwrap_or_default()),
+            elapsed,
+            t.status().name()
+        )?;
+    }
+    if let Some(timing) = timing {
+        writeln!(
+            out,
+            "|  |  |  |  | {:.2?} (summed {:.2?}) | total |",
+            timing.wall, timing.summed
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{render, DayReport, Format, InputReport, Status, TaskReport, Timing};
+    use crate::error::Error;
+
+    fn reports() -> Vec<DayReport> {
+        vec![DayReport {
+            day: 8,
+            input: Ok(InputReport {
+                load: Duration::from_micros(3),
+                parse: Duration::from_micros(40),
+                parse_stats: None,
+            }),
+            tasks: vec![
+                TaskReport {
+                    task: 1,
+                    result: Ok("1,753".to_string()),
+                    expected: Some("1,753".to_string()),
+                    elapsed: Some(Duration::from_micros(50)),
+                    stats: None,
+                },
+                TaskReport {
+                    task: 2,
+                    result: Err(Error::parse("unknown opcode `jpm`").at_line(14).in_day(8)),
+                    expected: None,
+                    elapsed: None,
+                    stats: None,
+                },
+                TaskReport {
+                    task: 3,
+                    result: Ok("7".to_string()),
+                    expected: Some("8".to_string()),
+                    elapsed: Some(Duration::from_micros(2)),
+                    stats: None,
+                },
+                TaskReport {
+                    task: 4,
+                    result: Ok("9".to_string()),
+                    expected: None,
+                    elapsed: Some(Duration::from_micros(2)),
+                    stats: None,
+                },
+            ],
+        }]
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(
+            render(Format::Text, &reports(), None),
+            "Day: 8
+Parsed input, elapsed: 40.00µs
+Finished task: 1, result: 1,753, elapsed: 50.00µs, check: pass
+Failed task: 2, error: day 8, line 14: unknown opcode `jpm`
+Finished task: 3, result: 7, elapsed: 2.00µs, check: fail (expected 8)
+Finished task: 4, result: 9, elapsed: 2.00µs
+"
+        );
+    }
+
+    #[test]
+    fn test_missing_input() {
+        let missing = Error::missing_input("no input found").in_day(12);
+        let days = vec![DayReport {
+            day: 12,
+            input: Err(missing.clone()),
+            tasks: vec![TaskReport {
+                task: 1,
+                result: Err(missing),
+                expected: None,
+                elapsed: None,
+                stats: None,
+            }],
+        }];
+        assert_eq!(days[0].tasks[0].status(), Status::Skipped);
+        assert_eq!(
+            render(Format::Text, &days, None),
+            "Day: 12\nSkipped, day 12: no input found\n"
+        );
+        assert_eq!(
+            render(Format::Csv, &days, None),
+            "day,task,status,answer,expected,elapsed_ns,parse_ns,error\n12,1,skipped,,,,,day 12: no input found\n"
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(
+            render(Format::Json, &reports(), None),
+            "[{\"day\":8,\"task\":1,\"status\":\"pass\",\"expected\":\"1,753\",\"answer\":\"1,753\",\"elapsed_ns\":50000,\"parse_ns\":40000},\
+{\"day\":8,\"task\":2,\"status\":\"error\",\"error\":\"day 8, line 14: unknown opcode `jpm`\",\"parse_ns\":40000},\
+{\"day\":8,\"task\":3,\"status\":\"fail\",\"expected\":\"8\",\"answer\":\"7\",\"elapsed_ns\":2000,\"parse_ns\":40000},\
+{\"day\":8,\"task\":4,\"status\":\"unknown\",\"answer\":\"9\",\"elapsed_ns\":2000,\"parse_ns\":40000}]
+"
+        );
+    }
+
+    #[test]
+    fn test_csv() {
+        assert_eq!(
+            render(Format::Csv, &reports(), None),
+            "day,task,status,answer,expected,elapsed_ns,parse_ns,error
+8,1,pass,\"1,753\",\"1,753\",50000,40000,
+8,2,error,,,,40000,\"day 8, line 14: unknown opcode `jpm`\"
+8,3,fail,7,8,2000,40000,
+8,4,unknown,9,,2000,40000,
+"
+        );
+    }
+
+    #[test]
+    fn test_markdown() {
+        assert_eq!(
+            render(Format::Markdown, &reports(), None),
+            "| Day | Task | Answer | Expected | Elapsed | Status |
+| --: | ---: | :----- | :------- | ------: | :----- |
+| 8 | 1 | 1,753 | 1,753 | 50.00µs | pass |
+| 8 | 2 | day 8, line 14: unknown opcode `jpm` |  |  | error |
+| 8 | 3 | 7 | 8 | 2.00µs | fail |
+| 8 | 4 | 9 |  | 2.00µs | unknown |
+"
+        );
+    }
+
+    #[test]
+    fn test_timing() {
+        let timing = Some(Timing {
+            wall: Duration::from_micros(60),
+            summed: Duration::from_micros(94),
+        });
+        let days = &reports()[..];
+        assert!(render(Format::Text, days, timing)
+            .ends_with("Total wall time: 60.00µs, summed time: 94.00µs\n"));
+        assert!(render(Format::Json, days, timing)
+            .ends_with(",{\"status\":\"total\",\"elapsed_ns\":60000,\"summed_ns\":94000}]\n"));
+        let csv = render(Format::Csv, days, timing);
+        assert!(csv
+            .starts_with("day,task,status,answer,expected,elapsed_ns,parse_ns,error,summed_ns\n"));
+        assert!(csv.contains("\n8,4,unknown,9,,2000,40000,,\n"));
+        assert!(csv.ends_with("\n,,total,,,60000,,,94000\n"));
+        assert!(render(Format::Markdown, days, timing)
+            .ends_with("\n|  |  |  |  | 60.00µs (summed 94.00µs) | total |\n"));
+    }
+}
@@ -1 +1,344 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Display;
+use std::str::{FromStr, Lines};
 
+use crate::error::Error;
+
+/// Parses each line of the input, tagging any failure with its 1-based line
+/// number.
+pub fn parse_lines<T>(
+    lines: Lines,
+    parse: impl Fn(&str) -> Result<T, Error>,
+) -> Result<Vec<T>, Error> {
+    lines
+        .enumerate()
+        .map(|(i, l)| parse(l).map_err(|e| e.at_line(i + 1)))
+        .collect()
+}
+
+pub fn parse_number<T: FromStr>(input: &str) -> Result<T, Error> {
+    input
+        .parse::<T>()
+        .map_err(|_| Error::parse(format!("invalid number `{}`", input)))
+}
+
+/// Intersects all the sets, or returns `None` if there are none.
+pub fn intersect_all<T: Ord + Clone>(
+    sets: impl IntoIterator<Item = BTreeSet<T>>,
+) -> Option<BTreeSet<T>> {
+    sets.into_iter()
+        .reduce(|a, b| a.intersection(&b).cloned().collect())
+}
+
+/// Assigns every key a distinct value from its candidates by elimination:
+/// a key with a single candidate left takes it, and that value is struck from
+/// every other key's candidates, until all keys are assigned. Fails if some
+/// key runs out of candidates, or if elimination stalls with keys that could
+/// still each take more than one value.
+pub fn eliminate<K: Ord + Clone + Display, V: Ord + Clone>(
+    mut candidates: BTreeMap<K, BTreeSet<V>>,
+) -> Result<BTreeMap<K, V>, Error> {
+    let mut assigned = BTreeMap::new();
+    while !candidates.is_empty() {
+        if let Some(key) = candidates.iter().find(|(_, c)| c.is_empty()).map(|e| e.0) {
+            return Err(Error::no_solution(format!(
+                "`{}` has no candidates left",
+                key
+            )));
+        }
+        let decided = candidates
+            .iter()
+            .find(|(_, c)| c.len() == 1)
+            .map(|(k, c)| (k.clone(), c.iter().next().unwrap().clone()));
+        let (key, value) = match decided {
+            Some(decided) => decided,
+            None => {
+                let keys: Vec<String> = candidates.keys().map(|k| format!("`{}`", k)).collect();
+                return Err(Error::no_solution(format!(
+                    "ambiguous constraints: {} could each take more than one value",
+                    keys.join(", ")
+                )));
+            }
+        };
+        candidates.remove(&key);
+        candidates.values_mut().for_each(|c| {
+            c.remove(&value);
+        });
+        assigned.insert(key, value);
+    }
+    Ok(assigned)
+}
+
+/// Computes `a * b % modulus` without overflowing, however large the operands.
+pub fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    (u128::from(a) * u128::from(b) % u128::from(modulus)) as u64
+}
+
+/// Computes `base^exponent % modulus` by repeated squaring.
+pub fn pow_mod(base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    let mut result = 1 % modulus;
+    let mut base = base % modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// The most baby steps [`discrete_log`] stores, which bounds its table to a
+/// few tens of megabytes whatever the modulus.
+const MAX_BABY_STEPS: u64 = 1 << 20;
+
+/// Finds the smallest `x` with `base^x ≡ target (mod modulus)` using baby-step
+/// giant-step, in about `sqrt(modulus)` steps and memory rather than the
+/// `modulus` steps of trying each power in turn. `base` must be coprime to
+/// `modulus`; returns `None` if it is not, or if no such `x` exists.
+///
+/// Above a modulus of 2^40 the table stops growing at 2^20 entries and the
+/// giant steps make up the difference, so memory stays bounded but the time
+/// grows as `modulus / 2^20` when `x` is large.
+pub fn discrete_log(base: u64, target: u64, modulus: u64) -> Option<u64> {
+    discrete_log_within(base, target, modulus, MAX_BABY_STEPS)
+}
+
+fn discrete_log_within(base: u64, target: u64, modulus: u64, max_baby_steps: u64) -> Option<u64> {
+    if modulus == 0 {
+        return None;
+    }
+    let (g, inverse, _) = extended_gcd(i128::from(base % modulus), i128::from(modulus));
+    if g != 1 {
+        return None;
+    }
+    let target = target % modulus;
+    let steps = ceil_sqrt(modulus).min(max_baby_steps);
+    // Baby steps: the first exponent below `steps` reaching each power.
+    let mut baby: HashMap<u64, u64> = HashMap::new();
+    let mut power = 1 % modulus;
+    for j in 0..steps {
+        baby.entry(power).or_insert(j);
+        power = mul_mod(power, base, modulus);
+    }
+    // Giant steps: divide the target by `base^steps` until it is a baby step.
+    let inverse = inverse.rem_euclid(i128::from(modulus)) as u64;
+    let giant = pow_mod(inverse, steps, modulus);
+    let mut value = target;
+    for i in 0..modulus.div_ceil(steps) {
+        if let Some(j) = baby.get(&value) {
+            return Some(i * steps + j);
+        }
+        value = mul_mod(value, giant, modulus);
+    }
+    None
+}
+
+/// The smallest `n` with `n * n >= value`. For the largest values that is
+/// 2^32, whose square does not fit in a `u64`.
+fn ceil_sqrt(value: u64) -> u64 {
+    let root = value.isqrt();
+    if root * root == value {
+        root
+    } else {
+        root + 1
+    }
+}
+
+/// Finds the same `x` as [`discrete_log`] by trying each power of `base` in
+/// turn. This takes up to `modulus` steps, but no extra memory, and `base`
+/// need not be coprime to `modulus`.
+pub fn discrete_log_brute_force(base: u64, target: u64, modulus: u64) -> Option<u64> {
+    if modulus == 0 {
+        return None;
+    }
+    let target = target % modulus;
+    let mut power = 1 % modulus;
+    for x in 0..modulus {
+        if power == target {
+            return Some(x);
+        }
+        power = mul_mod(power, base, modulus);
+    }
+    None
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)` with the Chinese
+/// Remainder Theorem. Moduli need not be coprime. Returns the smallest
+/// non-negative solution along with the modulus it repeats at, or `None` if
+/// the congruences contradict each other or that modulus overflows a `u64`.
+pub fn crt(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+    congruences
+        .iter()
+        .try_fold((0u64, 1u64), |(x, m), &(residue, modulus)| {
+            if modulus == 0 {
+                return None;
+            }
+            let residue = residue % modulus;
+            let (g, inverse, _) = extended_gcd(i128::from(m), i128::from(modulus));
+            let (g, inverse) = (g as u64, inverse.rem_euclid(i128::from(modulus)) as u64);
+            let diff = (i128::from(residue) - i128::from(x)).rem_euclid(i128::from(modulus)) as u64;
+            if !diff.is_multiple_of(g) {
+                return None;
+            }
+            let step = modulus / g;
+            let lcm = m.checked_mul(step)?;
+            let t = mul_mod(diff / g, inverse % step, step);
+            Some((
+                (u128::from(x) + u128::from(m) * u128::from(t)) as u64 % lcm,
+                lcm,
+            ))
+        })
+}
+
+/// Returns `(gcd, x, y)` such that `a * x + b * y == gcd`.
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - a / b * y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use crate::utils::{
+        ceil_sqrt, crt, discrete_log, discrete_log_brute_force, discrete_log_within, eliminate,
+        intersect_all, mul_mod, pow_mod,
+    };
+
+    fn candidates(entries: &[(&'static str, &[u32])]) -> BTreeMap<&'static str, BTreeSet<u32>> {
+        entries
+            .iter()
+            .map(|(k, c)| (*k, c.iter().copied().collect()))
+            .collect()
+    }
+
+    #[test]
+    fn test_intersect_all() {
+        let sets = [vec![1, 2, 3], vec![2, 3, 4], vec![3, 2]];
+        assert_eq!(
+            intersect_all(sets.iter().map(|s| s.iter().copied().collect())),
+            Some(BTreeSet::from([2, 3]))
+        );
+        assert_eq!(intersect_all(Vec::<BTreeSet<u32>>::new()), None);
+    }
+
+    #[test]
+    fn test_eliminate() {
+        assert_eq!(
+            eliminate(candidates(&[
+                ("a", &[1, 2, 3]),
+                ("b", &[2]),
+                ("c", &[2, 3])
+            ])),
+            Ok(BTreeMap::from([("a", 1), ("b", 2), ("c", 3)]))
+        );
+        assert_eq!(
+            eliminate(candidates(&[("a", &[1]), ("b", &[1])]))
+                .err()
+                .unwrap()
+                .message,
+            "`b` has no candidates left"
+        );
+        assert_eq!(
+            eliminate(candidates(&[("a", &[1, 2]), ("b", &[1, 2]), ("c", &[3])]))
+                .err()
+                .unwrap()
+                .message,
+            "ambiguous constraints: `a`, `b` could each take more than one value"
+        );
+    }
+
+    #[test]
+    fn test_mul_mod() {
+        assert_eq!(mul_mod(u64::MAX, u64::MAX, 1_000_000_007), 114944269);
+        assert_eq!(mul_mod(3, 4, 5), 2);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(1, u64::MAX), (0, 2)]), None);
+        let big = (1 << 61) - 1;
+        assert_eq!(crt(&[(5, big), (0, 3)]), Some((big + 5, 3 * big)));
+    }
+
+    #[test]
+    fn test_pow_mod() {
+        assert_eq!(pow_mod(7, 8, 20201227), 5764801);
+        assert_eq!(pow_mod(2, 0, 1), 0);
+        assert_eq!(pow_mod(u64::MAX, 3, 1_000_000_007), 722586148);
+    }
+
+    #[test]
+    fn test_discrete_log() {
+        assert_eq!(discrete_log(7, 5764801, 20201227), Some(8));
+        assert_eq!(discrete_log(7, 17807724, 20201227), Some(11));
+        assert_eq!(discrete_log(3, 1, 7), Some(0));
+        assert_eq!(discrete_log(2, 3, 7), None);
+        assert_eq!(discrete_log(2, 4, 8), None);
+        for x in 0..100 {
+            let target = pow_mod(5, x, 10007);
+            assert_eq!(discrete_log(5, target, 10007), Some(x));
+        }
+        let target = pow_mod(5, 999_999, 1_000_000_007);
+        assert_eq!(discrete_log(5, target, 1_000_000_007), Some(999_999));
+    }
+
+    #[test]
+    fn test_discrete_log_table_limit() {
+        // With at most 4 baby steps, moduli above 16 lean on extra giant steps.
+        for modulus in [13, 16, 17, 101] {
+            for target in 0..modulus {
+                assert_eq!(
+                    discrete_log_within(3, target, modulus, 4),
+                    discrete_log_brute_force(3, target, modulus),
+                    "3^x = {} (mod {})",
+                    target,
+                    modulus
+                );
+            }
+        }
+        // The largest 64-bit prime would want 2^32 baby steps.
+        let modulus = 18_446_744_073_709_551_557;
+        let target = pow_mod(3, 5_000_000, modulus);
+        assert_eq!(
+            discrete_log_within(3, target, modulus, 1 << 10),
+            Some(5_000_000)
+        );
+    }
+
+    #[test]
+    fn test_ceil_sqrt() {
+        assert_eq!(ceil_sqrt(0), 0);
+        assert_eq!(ceil_sqrt(1), 1);
+        assert_eq!(ceil_sqrt(10), 4);
+        assert_eq!(ceil_sqrt(16), 4);
+        assert_eq!(ceil_sqrt(u64::MAX), 1 << 32);
+    }
+
+    #[test]
+    fn test_discrete_log_brute_force() {
+        for (base, modulus) in [(3, 7), (2, 7), (10, 101), (7, 1009)] {
+            for target in [0, 1, 2, 6, 100, 1008] {
+                assert_eq!(
+                    discrete_log_brute_force(base, target, modulus),
+                    discrete_log(base, target, modulus),
+                    "{}^x = {} (mod {})",
+                    base,
+                    target,
+                    modulus
+                );
+            }
+        }
+        assert_eq!(discrete_log_brute_force(2, 0, 8), Some(3));
+        assert_eq!(discrete_log_brute_force(2, 3, 8), None);
+    }
+}